@@ -0,0 +1,290 @@
+use crate::cell::{CellValue, FileRef};
+use crate::store::{Board, Cells, TaskData, TrashedItem};
+use gloo_console::log;
+use js_sys::{Array, Promise};
+use std::cell::RefCell;
+use std::collections::HashSet;
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{Blob, File, IdbDatabase, IdbObjectStore, IdbRequest, IdbTransactionMode};
+
+/// Attached files are too big for localStorage, so they go to IndexedDB and
+/// the board only keeps a [`FileRef`] pointing at them.
+const DB_NAME: &str = "sample-monday.attachments";
+const DB_VERSION: u32 = 1;
+const STORE: &str = "files";
+
+thread_local! {
+    /// The open connection, shared by every read and write.
+    static DB: RefCell<Option<IdbDatabase>> = const { RefCell::new(None) };
+}
+
+/// Waits for an IndexedDB request to finish and returns its result.
+async fn finish(request: &IdbRequest) -> Result<JsValue, JsValue> {
+    let promise = Promise::new(&mut |resolve, reject| {
+        let on_success = {
+            let request = request.clone();
+            Closure::once_into_js(move || {
+                let result = request.result().unwrap_or(JsValue::UNDEFINED);
+                let _ = resolve.call1(&JsValue::UNDEFINED, &result);
+            })
+        };
+        let on_error = {
+            let request = request.clone();
+            Closure::once_into_js(move || {
+                let error = request
+                    .error()
+                    .ok()
+                    .flatten()
+                    .map(JsValue::from)
+                    .unwrap_or(JsValue::UNDEFINED);
+                let _ = reject.call1(&JsValue::UNDEFINED, &error);
+            })
+        };
+        request.set_onsuccess(Some(on_success.unchecked_ref()));
+        request.set_onerror(Some(on_error.unchecked_ref()));
+    });
+    JsFuture::from(promise).await
+}
+
+/// The database connection, opened on first use and kept for the page's
+/// lifetime.
+async fn open() -> Result<IdbDatabase, JsValue> {
+    if let Some(db) = DB.with(|db| db.borrow().clone()) {
+        return Ok(db);
+    }
+    let factory = web_sys::window()
+        .ok_or("no window")?
+        .indexed_db()?
+        .ok_or("IndexedDB is not available")?;
+    let request = factory.open_with_u32(DB_NAME, DB_VERSION)?;
+    let on_upgrade = {
+        let request = request.clone();
+        Closure::once_into_js(move || {
+            if let Ok(db) = request.result() {
+                let _ = db
+                    .unchecked_into::<IdbDatabase>()
+                    .create_object_store(STORE);
+            }
+        })
+    };
+    request.set_onupgradeneeded(Some(on_upgrade.unchecked_ref()));
+    let db: IdbDatabase = finish(&request).await?.unchecked_into();
+    // Another call may have opened one while this one waited.
+    Ok(DB.with(|cached| match &mut *cached.borrow_mut() {
+        Some(cached) => {
+            db.close();
+            cached.clone()
+        }
+        empty => empty.insert(db).clone(),
+    }))
+}
+
+async fn store(mode: IdbTransactionMode) -> Result<IdbObjectStore, JsValue> {
+    let transaction = match open().await?.transaction_with_str_and_mode(STORE, mode) {
+        Ok(transaction) => transaction,
+        // The browser closed the connection, such as when site data was
+        // cleared, so open a new one.
+        Err(_) => {
+            DB.with(|db| db.borrow_mut().take());
+            open().await?.transaction_with_str_and_mode(STORE, mode)?
+        }
+    };
+    transaction.object_store(STORE)
+}
+
+/// Saves `file` to IndexedDB and returns a reference to it.
+pub async fn put(file: &File) -> Result<FileRef, JsValue> {
+    // The key starts with when it was written, so garbage collection can
+    // leave alone files uploaded after it began.
+    let key = format!(
+        "{:x}-{:016x}",
+        js_sys::Date::now() as u64,
+        rand::random::<u64>()
+    );
+    let store = store(IdbTransactionMode::Readwrite).await?;
+    finish(&store.put_with_key(file, &JsValue::from_str(&key))?).await?;
+    Ok(FileRef {
+        name: file.name(),
+        key: Some(key),
+        mime: file.type_(),
+        size: file.size() as u64,
+    })
+}
+
+/// Loads a stored file, or `None` if it is no longer there.
+pub async fn get(key: &str) -> Result<Option<Blob>, JsValue> {
+    let store = store(IdbTransactionMode::Readonly).await?;
+    let result = finish(&store.get(&JsValue::from_str(key))?).await?;
+    Ok(result.dyn_into::<Blob>().ok())
+}
+
+fn file_keys<'a>(cells: &'a Cells, keys: &mut HashSet<&'a str>) {
+    for value in cells.values() {
+        if let CellValue::Files(files) = value {
+            keys.extend(files.iter().filter_map(|file| file.key.as_deref()));
+        }
+    }
+}
+
+/// Every stored file the board still points at, including from the trash and
+/// column defaults.
+fn referenced_keys(board: &Board) -> HashSet<&str> {
+    let mut keys = HashSet::new();
+    let mut tasks: Vec<&TaskData> = board.groups.iter().flat_map(|group| &group.tasks).collect();
+    for item in &board.trash {
+        match item {
+            TrashedItem::Group { group, .. } => tasks.extend(&group.tasks),
+            TrashedItem::Task { task, .. } => tasks.push(task),
+            TrashedItem::Subitem { subitem, .. } => file_keys(&subitem.cells, &mut keys),
+        }
+    }
+    for task in tasks {
+        file_keys(&task.cells, &mut keys);
+        for subitem in &task.subitems {
+            file_keys(&subitem.cells, &mut keys);
+        }
+    }
+    for column in board.columns.iter().chain(&board.subitem_columns) {
+        if let CellValue::Files(files) = &column.default {
+            keys.extend(files.iter().filter_map(|file| file.key.as_deref()));
+        }
+    }
+    keys
+}
+
+/// When the file under `key` was stored, in milliseconds since the epoch.
+/// `None` for keys written before they carried the time.
+fn written_at(key: &str) -> Option<u64> {
+    let (millis, _) = key.split_once('-')?;
+    u64::from_str_radix(millis, 16).ok()
+}
+
+/// Deletes stored files nothing on the board points at any more. Removing a
+/// file from a cell leaves it stored so undo can bring it back, so this only
+/// runs on load, when the undo history is empty.
+///
+/// Files stored after this starts are kept: `board` may predate the cell
+/// that points at them.
+pub async fn collect_garbage(board: &Board) {
+    let started = js_sys::Date::now() as u64;
+    let result: Result<(), JsValue> = async {
+        let referenced = referenced_keys(board);
+        // Listing and deleting in one transaction keeps uploads from
+        // landing in between.
+        let store = store(IdbTransactionMode::Readwrite).await?;
+        let keys: Array = finish(&store.get_all_keys()?).await?.unchecked_into();
+        for key in keys.iter() {
+            let Some(name) = key.as_string() else {
+                continue;
+            };
+            let keep = referenced.contains(name.as_str())
+                || written_at(&name).is_some_and(|written| written >= started);
+            if !keep {
+                store.delete(&key)?;
+            }
+        }
+        Ok(())
+    }
+    .await;
+    if let Err(err) = result {
+        log!("Could not clean up attachments:", err);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cell::CellKind;
+    use crate::column::ColumnScope;
+    use crate::store::SubitemData;
+
+    fn files(keys: &[&str]) -> CellValue {
+        CellValue::Files(
+            keys.iter()
+                .map(|key| FileRef {
+                    name: key.to_string(),
+                    key: Some(key.to_string()),
+                    ..FileRef::default()
+                })
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn referenced_keys_include_trash_and_defaults() {
+        let mut board = Board::default();
+        let column = board.column_of_kind(ColumnScope::Task, CellKind::Files);
+        let subitem_column = board.column_of_kind(ColumnScope::Subitem, CellKind::Files);
+        let subitem = |key| {
+            SubitemData::new(
+                0,
+                String::new(),
+                Cells::from([(subitem_column, files(&[key]))]),
+            )
+        };
+        let mut task = TaskData::new(0, String::new(), Cells::from([(column, files(&["live"]))]));
+        task.subitems.push(subitem("live subitem"));
+        board.groups[0].tasks.push(task.clone());
+
+        let mut group = board.groups[1].clone();
+        group.tasks.push(TaskData::new(
+            0,
+            String::new(),
+            Cells::from([(column, files(&["trashed group"]))]),
+        ));
+        task.cells.insert(column, files(&["trashed task"]));
+        task.subitems[0] = subitem("trashed task's subitem");
+        board.trash = vec![
+            TrashedItem::Group { index: 1, group },
+            TrashedItem::Task {
+                group: 0,
+                index: 0,
+                task,
+            },
+            TrashedItem::Subitem {
+                task: 0,
+                index: 0,
+                subitem: subitem("trashed subitem"),
+            },
+        ];
+        board
+            .columns
+            .iter_mut()
+            .find(|c| c.id == column)
+            .unwrap()
+            .default = files(&["default"]);
+        board
+            .subitem_columns
+            .iter_mut()
+            .find(|c| c.id == subitem_column)
+            .unwrap()
+            .default = files(&["subitem default"]);
+
+        let mut keys: Vec<&str> = referenced_keys(&board).into_iter().collect();
+        keys.sort_unstable();
+        assert_eq!(
+            keys,
+            [
+                "default",
+                "live",
+                "live subitem",
+                "subitem default",
+                "trashed group",
+                "trashed subitem",
+                "trashed task",
+                "trashed task's subitem",
+            ]
+        );
+    }
+
+    #[test]
+    fn keys_carry_when_they_were_written() {
+        assert_eq!(
+            written_at("18f3a2b4c00-00000000deadbeef"),
+            Some(0x18f3a2b4c00)
+        );
+        assert_eq!(written_at("00000000deadbeef"), None);
+    }
+}
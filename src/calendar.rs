@@ -0,0 +1,309 @@
+use crate::cell::{CellKind, CellValue};
+use crate::column::{ColumnDef, ColumnScope};
+use crate::date::today;
+use crate::detail::{use_open_item, ItemRef};
+use crate::drag;
+use crate::filter::BoardFilter;
+use crate::store::{use_board, Board, BoardAction};
+use chrono::{Datelike, Duration, Months, NaiveDate};
+use web_sys::DragEvent;
+use yew::prelude::*;
+
+/// A task or subitem placed on its date.
+#[derive(Clone, PartialEq, Debug)]
+struct Entry {
+    item: ItemRef,
+    name: String,
+    date: NaiveDate,
+    color: String,
+}
+
+/// The date column items of `scope` are placed by: the first one.
+fn date_column(board: &Board, scope: ColumnScope) -> Option<&ColumnDef> {
+    board
+        .columns_for(scope)
+        .iter()
+        .find(|column| column.kind == CellKind::Date)
+}
+
+/// Every task and subitem the filter shows that has a date, colored by its
+/// group.
+fn entries(board: &Board, filter: &BoardFilter) -> Vec<Entry> {
+    let date = |scope: ColumnScope, cells| match date_column(board, scope)?.cell(cells) {
+        CellValue::Date(date) => date,
+        _ => None,
+    };
+    let mut entries = Vec::new();
+    for group in &board.groups {
+        for task in filter.visible_tasks(board, &group.tasks) {
+            if let Some(date) = date(ColumnScope::Task, &task.cells) {
+                entries.push(Entry {
+                    item: ItemRef {
+                        scope: ColumnScope::Task,
+                        id: task.id,
+                    },
+                    name: task.name.clone(),
+                    date,
+                    color: group.color.clone(),
+                });
+            }
+            for subitem in filter.visible_subitems(board, task) {
+                if let Some(date) = date(ColumnScope::Subitem, &subitem.cells) {
+                    entries.push(Entry {
+                        item: ItemRef {
+                            scope: ColumnScope::Subitem,
+                            id: subitem.id,
+                        },
+                        name: subitem.name.clone(),
+                        date,
+                        color: group.color.clone(),
+                    });
+                }
+            }
+        }
+    }
+    entries
+}
+
+/// How much of the calendar is shown at once.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Span {
+    Month,
+    Week,
+}
+
+/// The Monday of the week `date` falls in.
+fn week_start(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(date.weekday().num_days_from_monday() as i64)
+}
+
+impl Span {
+    /// The days to show for the month or week containing `anchor`, in whole
+    /// weeks from Monday.
+    fn days(self, anchor: NaiveDate) -> Vec<NaiveDate> {
+        let (first, last) = match self {
+            Span::Month => {
+                let first = anchor.with_day(1).unwrap_or(anchor);
+                let last = first + Months::new(1) - Duration::days(1);
+                (week_start(first), week_start(last) + Duration::days(6))
+            }
+            Span::Week => (week_start(anchor), week_start(anchor) + Duration::days(6)),
+        };
+        first.iter_days().take_while(|day| *day <= last).collect()
+    }
+
+    /// The anchor of the next or previous month or week.
+    fn step(self, anchor: NaiveDate, forward: bool) -> NaiveDate {
+        match (self, forward) {
+            (Span::Month, true) => anchor + Months::new(1),
+            (Span::Month, false) => anchor - Months::new(1),
+            (Span::Week, true) => anchor + Duration::days(7),
+            (Span::Week, false) => anchor - Duration::days(7),
+        }
+    }
+
+    fn title(self, anchor: NaiveDate) -> String {
+        match self {
+            Span::Month => anchor.format("%B %Y").to_string(),
+            Span::Week => week_start(anchor).format("Week of %b %-d, %Y").to_string(),
+        }
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct CalendarProps {
+    #[prop_or_default]
+    pub filter: BoardFilter,
+}
+
+/// The board's tasks and subitems on a month or week calendar by their date
+/// column. Drag one to another day to reschedule it, or click it to open it.
+#[function_component(Calendar)]
+pub fn calendar(props: &CalendarProps) -> Html {
+    let board = use_board();
+    let open_item = use_open_item();
+    let span = use_state(|| Span::Month);
+    let anchor = use_state(today);
+    let dragging = use_state(|| None::<ItemRef>);
+    // Dragover fires many times a second, so only a new day re-renders.
+    let hovered = use_state_eq(|| None::<NaiveDate>);
+
+    if date_column(&board, ColumnScope::Task).is_none()
+        && date_column(&board, ColumnScope::Subitem).is_none()
+    {
+        return html! {
+            <p class="text-sm text-gray-500">{"Add a date column to see the board on a calendar."}</p>
+        };
+    }
+
+    let on_step = |forward: bool| {
+        let anchor = anchor.clone();
+        let span = *span;
+        Callback::from(move |_| anchor.set(span.step(*anchor, forward)))
+    };
+    let on_today = {
+        let anchor = anchor.clone();
+        Callback::from(move |_| anchor.set(today()))
+    };
+    let span_button = |to: Span, label: &str| {
+        let class = if *span == to {
+            "px-3 py-1 bg-blue-500 text-white"
+        } else {
+            "px-3 py-1 bg-white"
+        };
+        let span = span.clone();
+        html! { <button onclick={Callback::from(move |_| span.set(to))} class={class}>{ label }</button> }
+    };
+
+    let entries = entries(&board, &props.filter);
+    let days = span.days(*anchor);
+    let today = today();
+
+    let chip = |entry: &Entry| {
+        let item = entry.item;
+        let on_open = {
+            let open_item = open_item.clone();
+            Callback::from(move |_| open_item.set(Some(item)))
+        };
+        let on_drag_start = {
+            let dragging = dragging.clone();
+            Callback::from(move |e: DragEvent| {
+                drag::start(&e, item.id);
+                dragging.set(Some(item));
+            })
+        };
+        let on_drag_end = {
+            let dragging = dragging.clone();
+            let hovered = hovered.clone();
+            Callback::from(move |_: DragEvent| {
+                dragging.set(None);
+                hovered.set(None);
+            })
+        };
+        let subitem = item.scope == ColumnScope::Subitem;
+        html! {
+            <li key={format!("{:?}", item)}>
+                <button
+                    class={format!(
+                        "block w-full px-1 text-xs text-left truncate rounded cursor-grab {}",
+                        if subitem {
+                            format!("bg-white border border-{}-500 text-{}-700", entry.color, entry.color)
+                        } else {
+                            format!("bg-{}-500 text-white", entry.color)
+                        },
+                    )}
+                    title={entry.name.clone()}
+                    draggable="true"
+                    onclick={on_open}
+                    ondragstart={on_drag_start}
+                    ondragend={on_drag_end}
+                >
+                    { if subitem { format!("↳ {}", entry.name) } else { entry.name.clone() } }
+                </button>
+            </li>
+        }
+    };
+
+    html! {
+        <div>
+            <div class="flex items-center space-x-2">
+                <button onclick={on_step(false)} class="px-2 py-1 border rounded" title="Previous">{"‹"}</button>
+                <button onclick={on_today} class="px-2 py-1 border rounded">{"Today"}</button>
+                <button onclick={on_step(true)} class="px-2 py-1 border rounded" title="Next">{"›"}</button>
+                <h2 class="text-lg font-semibold">{ span.title(*anchor) }</h2>
+                <span class="inline-flex overflow-hidden border rounded divide-x">
+                    { span_button(Span::Month, "Month") }
+                    { span_button(Span::Week, "Week") }
+                </span>
+            </div>
+            <div class="grid grid-cols-7 mt-4 text-xs font-semibold text-gray-500">
+                { for ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"].iter().map(|day| html! {
+                    <div class="px-2 py-1">{ *day }</div>
+                }) }
+            </div>
+            <div class="grid grid-cols-7 border-t border-l">
+                { for days.iter().map(|&day| {
+                    let on_drag_over = {
+                        let hovered = hovered.clone();
+                        Callback::from(move |e: DragEvent| {
+                            // Without this the day doesn't accept the drop.
+                            e.prevent_default();
+                            hovered.set(Some(day));
+                        })
+                    };
+                    let on_drag_leave = {
+                        let hovered = hovered.clone();
+                        Callback::from(move |e: DragEvent| {
+                            if drag::left(&e) {
+                                hovered.set(None);
+                            }
+                        })
+                    };
+                    let on_drop = {
+                        let board = board.clone();
+                        let dragging = dragging.clone();
+                        let hovered = hovered.clone();
+                        Callback::from(move |e: DragEvent| {
+                            e.prevent_default();
+                            if let Some(item) = *dragging {
+                                if let Some(column) = date_column(&board, item.scope).map(|column| column.id) {
+                                    let value = CellValue::Date(Some(day));
+                                    board.dispatch(match item.scope {
+                                        ColumnScope::Task => BoardAction::UpdateTask {
+                                            task: item.id,
+                                            column,
+                                            value,
+                                        },
+                                        ColumnScope::Subitem => BoardAction::UpdateSubitem {
+                                            subitem: item.id,
+                                            column,
+                                            value,
+                                        },
+                                    });
+                                }
+                            }
+                            dragging.set(None);
+                            hovered.set(None);
+                        })
+                    };
+                    let outside = *span == Span::Month && day.month() != anchor.month();
+                    let background = if *hovered == Some(day) {
+                        "bg-blue-50"
+                    } else if outside {
+                        "bg-gray-50"
+                    } else {
+                        "bg-white"
+                    };
+                    html! {
+                        <div
+                            key={day.to_string()}
+                            class={classes!(
+                                "p-1", "border-r", "border-b", background,
+                                if *span == Span::Week { "min-h-64" } else { "min-h-24" },
+                            )}
+                            ondragover={on_drag_over}
+                            ondragleave={on_drag_leave}
+                            ondrop={on_drop}
+                        >
+                            <div class={classes!(
+                                "w-6", "h-6", "mb-1", "text-xs", "leading-6", "text-center", "rounded-full",
+                                if day == today {
+                                    "bg-blue-500 text-white"
+                                } else if outside {
+                                    "text-gray-400"
+                                } else {
+                                    "text-gray-700"
+                                },
+                            )}>
+                                { day.day() }
+                            </div>
+                            <ul class="space-y-1">
+                                { for entries.iter().filter(|entry| entry.date == day).map(chip) }
+                            </ul>
+                        </div>
+                    }
+                }) }
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,747 @@
+use crate::attachments;
+use crate::cell::{CellKind, FileRef};
+use crate::column::ColumnScope;
+use crate::store::{use_board, BoardAction, Id};
+use gloo_events::EventListener;
+use js_sys::{Array, Promise};
+use std::cell::RefCell;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::{Clamped, JsCast, JsValue};
+use wasm_bindgen_futures::{spawn_local, JsFuture};
+use web_sys::{
+    Blob, CanvasRenderingContext2d, ClipboardEvent, File, FilePropertyBag, HtmlCanvasElement,
+    HtmlImageElement, HtmlInputElement, HtmlSelectElement, HtmlVideoElement, ImageData,
+    MediaStream, MediaStreamConstraints, MediaStreamTrack, Url,
+};
+use yew::prelude::*;
+
+/// Widths a capture can be scaled down to before it is saved. `None` keeps
+/// the cropped size.
+const MAX_WIDTHS: [Option<u32>; 4] = [Some(1920), Some(1280), Some(640), None];
+
+/// A still image as RGBA bytes, row by row.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Frame {
+    pub width: u32,
+    pub height: u32,
+    pub rgba: Vec<u8>,
+}
+
+/// A frame compared by identity rather than pixel by pixel, so hooks keyed
+/// on one stay cheap.
+#[derive(Clone)]
+struct Same<T>(Rc<T>);
+
+impl<T> PartialEq for Same<T> {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+/// How much of each edge to cut off, as a fraction of the width or height.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct Crop {
+    pub left: f64,
+    pub top: f64,
+    pub right: f64,
+    pub bottom: f64,
+}
+
+impl Frame {
+    fn pixel(&self, x: u32, y: u32) -> [f64; 4] {
+        let at = ((y * self.width + x) * 4) as usize;
+        [0, 1, 2, 3].map(|channel| self.rgba[at + channel] as f64)
+    }
+
+    /// The part of the frame left after `crop`, at least one pixel across.
+    pub fn crop(&self, crop: Crop) -> Frame {
+        let edge = |fraction: f64, size: u32| {
+            ((fraction.clamp(0.0, 1.0) * size as f64).round() as u32).min(size.saturating_sub(1))
+        };
+        let left = edge(crop.left, self.width);
+        let top = edge(crop.top, self.height);
+        let width = (self.width - left)
+            .saturating_sub(edge(crop.right, self.width))
+            .max(1);
+        let height = (self.height - top)
+            .saturating_sub(edge(crop.bottom, self.height))
+            .max(1);
+        let mut rgba = Vec::with_capacity((width * height * 4) as usize);
+        for y in top..top + height {
+            let start = ((y * self.width + left) * 4) as usize;
+            rgba.extend_from_slice(&self.rgba[start..start + (width * 4) as usize]);
+        }
+        Frame {
+            width,
+            height,
+            rgba,
+        }
+    }
+
+    /// Scales the frame to `width` by `height` with bilinear filtering.
+    pub fn resize(&self, width: u32, height: u32) -> Frame {
+        let width = width.max(1);
+        let height = height.max(1);
+        let scale_x = self.width as f64 / width as f64;
+        let scale_y = self.height as f64 / height as f64;
+        let mut rgba = Vec::with_capacity((width * height * 4) as usize);
+        for y in 0..height {
+            let source_y = ((y as f64 + 0.5) * scale_y - 0.5).clamp(0.0, (self.height - 1) as f64);
+            let y0 = source_y.floor() as u32;
+            let y1 = (y0 + 1).min(self.height - 1);
+            let dy = source_y - y0 as f64;
+            for x in 0..width {
+                let source_x =
+                    ((x as f64 + 0.5) * scale_x - 0.5).clamp(0.0, (self.width - 1) as f64);
+                let x0 = source_x.floor() as u32;
+                let x1 = (x0 + 1).min(self.width - 1);
+                let dx = source_x - x0 as f64;
+                let (a, b) = (self.pixel(x0, y0), self.pixel(x1, y0));
+                let (c, d) = (self.pixel(x0, y1), self.pixel(x1, y1));
+                for channel in 0..4 {
+                    let top = a[channel] + (b[channel] - a[channel]) * dx;
+                    let bottom = c[channel] + (d[channel] - c[channel]) * dx;
+                    rgba.push((top + (bottom - top) * dy).round() as u8);
+                }
+            }
+        }
+        Frame {
+            width,
+            height,
+            rgba,
+        }
+    }
+
+    /// Scales the frame down, keeping its shape, so it is at most
+    /// `max_width` wide. Narrower frames are left alone.
+    pub fn fit_width(&self, max_width: u32) -> Frame {
+        if self.width <= max_width {
+            return self.clone();
+        }
+        let height = (self.height as f64 * max_width as f64 / self.width as f64).round() as u32;
+        self.resize(max_width, height)
+    }
+}
+
+/// Work a [`MediaSource`] finishes later, failing with a message for the user.
+pub type Pending<'a, T> = Pin<Box<dyn Future<Output = Result<T, String>> + 'a>>;
+
+/// Somewhere still images can be taken from.
+pub trait MediaSource {
+    /// Starts the source. Sources with a live picture show it in `preview`.
+    fn start<'a>(&'a self, preview: &'a HtmlVideoElement) -> Pending<'a, ()>;
+
+    /// Takes a still of what the source currently shows.
+    fn snapshot(&self) -> Pending<'_, Frame>;
+
+    /// Releases the source, e.g. turning the camera off.
+    fn stop(&self);
+}
+
+/// The user's camera, through `getUserMedia`.
+#[derive(Default)]
+pub struct CameraSource {
+    stream: RefCell<Option<MediaStream>>,
+    video: RefCell<Option<HtmlVideoElement>>,
+}
+
+impl MediaSource for CameraSource {
+    fn start<'a>(&'a self, preview: &'a HtmlVideoElement) -> Pending<'a, ()> {
+        Box::pin(async move {
+            let devices = web_sys::window()
+                .ok_or("no window")?
+                .navigator()
+                .media_devices()
+                .map_err(describe)?;
+            let constraints = MediaStreamConstraints::new();
+            constraints.set_video(&JsValue::TRUE);
+            let stream: MediaStream = JsFuture::from(
+                devices
+                    .get_user_media_with_constraints(&constraints)
+                    .map_err(describe)?,
+            )
+            .await
+            .map_err(describe)?
+            .unchecked_into();
+            preview.set_src_object(Some(&stream));
+            JsFuture::from(preview.play().map_err(describe)?)
+                .await
+                .map_err(describe)?;
+            *self.stream.borrow_mut() = Some(stream);
+            *self.video.borrow_mut() = Some(preview.clone());
+            Ok(())
+        })
+    }
+
+    fn snapshot(&self) -> Pending<'_, Frame> {
+        Box::pin(async move {
+            let video = self
+                .video
+                .borrow()
+                .clone()
+                .ok_or("Start the camera first")?;
+            let (width, height) = (video.video_width(), video.video_height());
+            if width == 0 || height == 0 {
+                return Err("The camera has no picture yet".to_string());
+            }
+            draw(width, height, |context| {
+                context.draw_image_with_html_video_element(&video, 0.0, 0.0)
+            })
+            .map_err(describe)
+        })
+    }
+
+    fn stop(&self) {
+        if let Some(stream) = self.stream.borrow_mut().take() {
+            for track in stream.get_tracks().iter() {
+                track.unchecked_into::<MediaStreamTrack>().stop();
+            }
+        }
+        if let Some(video) = self.video.borrow_mut().take() {
+            video.set_src_object(None);
+        }
+    }
+}
+
+/// A stand-in camera that always shows the same test pattern, for headless
+/// test runs and machines without a camera. Open the board with
+/// `?fake-media` to use it.
+pub struct FakeSource {
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Default for FakeSource {
+    fn default() -> Self {
+        Self {
+            width: 640,
+            height: 480,
+        }
+    }
+}
+
+impl FakeSource {
+    /// Color bars over a horizontal gradient.
+    pub fn frame(&self) -> Frame {
+        const BARS: [[u8; 3]; 6] = [
+            [255, 255, 255],
+            [255, 255, 0],
+            [0, 255, 255],
+            [0, 255, 0],
+            [255, 0, 255],
+            [255, 0, 0],
+        ];
+        let mut rgba = Vec::with_capacity((self.width * self.height * 4) as usize);
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let [r, g, b] = if y < self.height * 2 / 3 {
+                    BARS[(x * BARS.len() as u32 / self.width) as usize]
+                } else {
+                    let level = (x * 255 / self.width.max(1)) as u8;
+                    [level, level, level]
+                };
+                rgba.extend_from_slice(&[r, g, b, 255]);
+            }
+        }
+        Frame {
+            width: self.width,
+            height: self.height,
+            rgba,
+        }
+    }
+}
+
+impl MediaSource for FakeSource {
+    fn start<'a>(&'a self, _preview: &'a HtmlVideoElement) -> Pending<'a, ()> {
+        Box::pin(async { Ok(()) })
+    }
+
+    fn snapshot(&self) -> Pending<'_, Frame> {
+        Box::pin(async move { Ok(self.frame()) })
+    }
+
+    fn stop(&self) {}
+}
+
+/// The fake source when the page asks for it, otherwise the camera.
+fn media_source() -> Rc<dyn MediaSource> {
+    let wants_fake = web_sys::window()
+        .and_then(|window| window.location().search().ok())
+        .is_some_and(|search| search.contains("fake-media"));
+    if wants_fake {
+        Rc::new(FakeSource::default())
+    } else {
+        Rc::new(CameraSource::default())
+    }
+}
+
+fn describe(err: JsValue) -> String {
+    err.as_string()
+        .or_else(|| {
+            js_sys::Reflect::get(&err, &"message".into())
+                .ok()
+                .and_then(|message| message.as_string())
+        })
+        .unwrap_or_else(|| "Something went wrong".to_string())
+}
+
+fn canvas(
+    width: u32,
+    height: u32,
+) -> Result<(HtmlCanvasElement, CanvasRenderingContext2d), JsValue> {
+    let canvas: HtmlCanvasElement = web_sys::window()
+        .and_then(|window| window.document())
+        .ok_or("no document")?
+        .create_element("canvas")?
+        .unchecked_into();
+    canvas.set_width(width);
+    canvas.set_height(height);
+    let context = canvas
+        .get_context("2d")?
+        .ok_or("no 2d canvas")?
+        .unchecked_into();
+    Ok((canvas, context))
+}
+
+/// Paints with `paint` on a blank canvas of the given size and reads the
+/// result back as a frame.
+fn draw(
+    width: u32,
+    height: u32,
+    paint: impl FnOnce(&CanvasRenderingContext2d) -> Result<(), JsValue>,
+) -> Result<Frame, JsValue> {
+    let (_, context) = canvas(width, height)?;
+    paint(&context)?;
+    let data = context.get_image_data(0.0, 0.0, width as f64, height as f64)?;
+    Ok(Frame {
+        width,
+        height,
+        rgba: data.data().0,
+    })
+}
+
+/// Puts `frame` on `canvas`, resizing the canvas to fit.
+fn show(frame: &Frame, canvas: &HtmlCanvasElement) -> Result<(), JsValue> {
+    canvas.set_width(frame.width);
+    canvas.set_height(frame.height);
+    let context: CanvasRenderingContext2d = canvas
+        .get_context("2d")?
+        .ok_or("no 2d canvas")?
+        .unchecked_into();
+    let data = ImageData::new_with_u8_clamped_array_and_sh(
+        Clamped(&frame.rgba),
+        frame.width,
+        frame.height,
+    )?;
+    context.put_image_data(&data, 0.0, 0.0)
+}
+
+/// Decodes a pasted or picked image file into a frame.
+async fn decode(blob: &Blob) -> Result<Frame, JsValue> {
+    let url = Url::create_object_url_with_blob(blob)?;
+    let image = HtmlImageElement::new()?;
+    image.set_src(&url);
+    let decoded = JsFuture::from(image.decode()).await;
+    Url::revoke_object_url(&url)?;
+    decoded?;
+    draw(image.natural_width(), image.natural_height(), |context| {
+        context.draw_image_with_html_image_element(&image, 0.0, 0.0)
+    })
+}
+
+/// Encodes `frame` as a PNG file.
+async fn encode(frame: &Frame, name: &str) -> Result<File, JsValue> {
+    let (canvas, _) = canvas(frame.width, frame.height)?;
+    show(frame, &canvas)?;
+    let blob = JsFuture::from(Promise::new(&mut |resolve, _| {
+        let callback = Closure::once_into_js(move |blob: JsValue| {
+            let _ = resolve.call1(&JsValue::UNDEFINED, &blob);
+        });
+        let _ = canvas.to_blob_with_type(callback.unchecked_ref(), "image/png");
+    }))
+    .await?;
+    let options = FilePropertyBag::new();
+    options.set_type("image/png");
+    File::new_with_blob_sequence_and_options(&Array::of1(&blob), name, &options)
+}
+
+#[derive(Properties, PartialEq)]
+pub struct CaptureDialogProps {
+    pub scope: ColumnScope,
+    /// The task or subitem the capture is attached to.
+    pub item: Id,
+    pub on_close: Callback<()>,
+}
+
+/// Takes a still from the camera or a pasted screenshot, lets the user crop
+/// and shrink it, and attaches it to the item's first files column.
+#[function_component(CaptureDialog)]
+pub fn capture_dialog(props: &CaptureDialogProps) -> Html {
+    let board = use_board();
+    let source = use_memo((), |_| media_source());
+    let video = use_node_ref();
+    let preview = use_node_ref();
+    let frame = use_state(|| None::<Rc<Frame>>);
+    let crop = use_state(Crop::default);
+    let max_width = use_state(|| MAX_WIDTHS[1]);
+    let is_live = use_state(|| false);
+    let is_saving = use_state(|| false);
+    let error = use_state(|| None::<String>);
+
+    let column = board
+        .columns_for(props.scope)
+        .iter()
+        .find(|column| column.kind == CellKind::Files)
+        .map(|column| column.id);
+
+    let result = use_memo(
+        (frame.as_ref().cloned().map(Same), *crop, *max_width),
+        |(frame, crop, max_width)| {
+            frame.as_ref().map(|frame| {
+                let cropped = frame.0.crop(*crop);
+                Rc::new(match *max_width {
+                    Some(max_width) => cropped.fit_width(max_width),
+                    None => cropped,
+                })
+            })
+        },
+    );
+
+    {
+        let preview = preview.clone();
+        use_effect_with((*result).clone().map(Same), move |result| {
+            if let (Some(result), Some(canvas)) = (result, preview.cast::<HtmlCanvasElement>()) {
+                let _ = show(&result.0, &canvas);
+            }
+        });
+    }
+
+    {
+        let source = source.clone();
+        use_effect_with((), move |_| move || source.stop());
+    }
+
+    // Screenshots can be pasted anywhere while the dialog is open.
+    {
+        let frame = frame.clone();
+        let error = error.clone();
+        use_effect_with((), move |_| {
+            let document = web_sys::window()
+                .and_then(|window| window.document())
+                .expect("document should be available");
+            let listener = EventListener::new(&document, "paste", move |e| {
+                let Some(image) = e
+                    .dyn_ref::<ClipboardEvent>()
+                    .and_then(|e| e.clipboard_data())
+                    .and_then(|data| data.files())
+                    .and_then(|files| {
+                        (0..files.length())
+                            .filter_map(|i| files.get(i))
+                            .find(|file| file.type_().starts_with("image/"))
+                    })
+                else {
+                    return;
+                };
+                e.prevent_default();
+                let frame = frame.clone();
+                let error = error.clone();
+                spawn_local(async move {
+                    match decode(&image).await {
+                        Ok(pasted) => {
+                            error.set(None);
+                            frame.set(Some(Rc::new(pasted)));
+                        }
+                        Err(err) => error.set(Some(describe(err))),
+                    }
+                });
+            });
+            move || drop(listener)
+        });
+    }
+
+    let on_start = {
+        let source = source.clone();
+        let video = video.clone();
+        let is_live = is_live.clone();
+        let error = error.clone();
+        Callback::from(move |_| {
+            let Some(video) = video.cast::<HtmlVideoElement>() else {
+                return;
+            };
+            let source = source.clone();
+            let is_live = is_live.clone();
+            let error = error.clone();
+            spawn_local(async move {
+                match source.start(&video).await {
+                    Ok(()) => {
+                        error.set(None);
+                        is_live.set(true);
+                    }
+                    Err(message) => error.set(Some(message)),
+                }
+            });
+        })
+    };
+
+    let on_snapshot = {
+        let source = source.clone();
+        let frame = frame.clone();
+        let error = error.clone();
+        Callback::from(move |_| {
+            let source = source.clone();
+            let frame = frame.clone();
+            let error = error.clone();
+            spawn_local(async move {
+                match source.snapshot().await {
+                    Ok(snapshot) => {
+                        error.set(None);
+                        frame.set(Some(Rc::new(snapshot)));
+                    }
+                    Err(message) => error.set(Some(message)),
+                }
+            });
+        })
+    };
+
+    let on_crop = |set: fn(&mut Crop, f64)| {
+        let crop = crop.clone();
+        Callback::from(move |e: InputEvent| {
+            if let Some(input) = e.target_dyn_into::<HtmlInputElement>() {
+                if let Ok(percent) = input.value().parse::<f64>() {
+                    let mut next = *crop;
+                    set(&mut next, percent / 100.0);
+                    crop.set(next);
+                }
+            }
+        })
+    };
+
+    let on_max_width = {
+        let max_width = max_width.clone();
+        Callback::from(move |e: Event| {
+            if let Some(select) = e.target_dyn_into::<HtmlSelectElement>() {
+                if let Some(width) = MAX_WIDTHS.get(select.selected_index() as usize) {
+                    max_width.set(*width);
+                }
+            }
+        })
+    };
+
+    let on_save = {
+        let dispatcher = board.dispatcher();
+        let scope = props.scope;
+        let item = props.item;
+        let on_close = props.on_close.clone();
+        let is_saving = is_saving.clone();
+        let error = error.clone();
+        let result = result.clone();
+        Callback::from(move |_| {
+            let Some(result) = (*result).clone().filter(|_| column.is_some()) else {
+                return;
+            };
+            let dispatcher = dispatcher.clone();
+            let on_close = on_close.clone();
+            let is_saving = is_saving.clone();
+            let error = error.clone();
+            is_saving.set(true);
+            spawn_local(async move {
+                let name = format!(
+                    "Capture {}.png",
+                    chrono::Local::now().format("%Y-%m-%d %H.%M.%S")
+                );
+                let saved: Result<FileRef, JsValue> = async {
+                    let file = encode(&result, &name).await?;
+                    attachments::put(&file).await
+                }
+                .await;
+                is_saving.set(false);
+                let file = match saved {
+                    Ok(file) => file,
+                    Err(err) => {
+                        error.set(Some(describe(err)));
+                        return;
+                    }
+                };
+                // The board may have changed while the file saved, so find
+                // the files column, and the cell in it, as they are now.
+                dispatcher.dispatch(BoardAction::AttachFiles {
+                    scope,
+                    item,
+                    column: None,
+                    files: vec![file],
+                });
+                on_close.emit(());
+            });
+        })
+    };
+
+    let on_close = {
+        let on_close = props.on_close.clone();
+        Callback::from(move |_| on_close.emit(()))
+    };
+
+    let slider = |label: &str, value: f64, oninput: Callback<InputEvent>| {
+        html! {
+            <label class="flex items-center space-x-2">
+                <span class="w-14">{ label }</span>
+                <input type="range" min="0" max="45" value={((value * 100.0).round()).to_string()} {oninput} />
+            </label>
+        }
+    };
+
+    html! {
+        <div class="fixed inset-0 z-20 flex items-center justify-center bg-black bg-opacity-60">
+            <div class="flex flex-col w-3/4 max-h-screen p-4 space-y-3 bg-white rounded-lg shadow-lg text-sm">
+                <div class="flex items-center">
+                    <span class="flex-1 font-semibold">{"Capture image"}</span>
+                    <button class="px-2" onclick={on_close}>{"✕"}</button>
+                </div>
+                { if column.is_none() {
+                    html! { <p class="text-red-600">{"Add a files column to attach captures."}</p> }
+                } else {
+                    html! {}
+                }}
+                <p class="text-gray-600">{"Paste a screenshot (Ctrl+V), or take a snapshot with the camera."}</p>
+                <div class="flex space-x-4">
+                    <div class="flex flex-col w-1/2 space-y-2">
+                        <video ref={video} class="w-full bg-black rounded" autoplay=true muted=true playsinline=true />
+                        <div class="space-x-2">
+                            <button class="p-1 px-2 border rounded disabled:text-gray-400" disabled={*is_live} onclick={on_start}>{"Start camera"}</button>
+                            <button class="p-1 px-2 border rounded disabled:text-gray-400" disabled={!*is_live} onclick={on_snapshot}>{"Take snapshot"}</button>
+                        </div>
+                    </div>
+                    <div class="flex flex-col w-1/2 space-y-2">
+                        <canvas ref={preview} class={classes!("max-w-full", "border", "rounded", result.is_none().then_some("hidden"))} />
+                        { if let Some(result) = &*result {
+                            html! {
+                                <>
+                                    <span class="text-gray-500">{ format!("{} × {} px", result.width, result.height) }</span>
+                                    { slider("Left", crop.left, on_crop(|crop, value| crop.left = value)) }
+                                    { slider("Top", crop.top, on_crop(|crop, value| crop.top = value)) }
+                                    { slider("Right", crop.right, on_crop(|crop, value| crop.right = value)) }
+                                    { slider("Bottom", crop.bottom, on_crop(|crop, value| crop.bottom = value)) }
+                                    <label class="flex items-center space-x-2">
+                                        <span class="w-14">{"Width"}</span>
+                                        <select class="border rounded p-1" onchange={on_max_width}>
+                                            { for MAX_WIDTHS.into_iter().map(|width| html! {
+                                                <option selected={width == *max_width}>
+                                                    { width.map_or("Original".to_string(), |width| format!("At most {} px", width)) }
+                                                </option>
+                                            }) }
+                                        </select>
+                                    </label>
+                                </>
+                            }
+                        } else {
+                            html! { <span class="text-gray-500">{"Nothing captured yet."}</span> }
+                        }}
+                    </div>
+                </div>
+                { if let Some(message) = &*error {
+                    html! { <span class="text-red-600">{ message }</span> }
+                } else {
+                    html! {}
+                }}
+                <div>
+                    <button
+                        class="p-1 px-2 bg-blue-500 text-white rounded disabled:bg-gray-300"
+                        disabled={result.is_none() || column.is_none() || *is_saving}
+                        onclick={on_save}
+                    >
+                        { if *is_saving { "Saving..." } else { "Attach" } }
+                    </button>
+                </div>
+            </div>
+        </div>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WHITE: [u8; 4] = [255, 255, 255, 255];
+    const YELLOW: [u8; 4] = [255, 255, 0, 255];
+    const RED: [u8; 4] = [255, 0, 0, 255];
+
+    /// Six 10-pixel bars over a 30-pixel-high frame, the bottom third a
+    /// gradient.
+    fn pattern() -> Frame {
+        FakeSource {
+            width: 60,
+            height: 30,
+        }
+        .frame()
+    }
+
+    fn pixel(frame: &Frame, x: u32, y: u32) -> [u8; 4] {
+        let at = ((y * frame.width + x) * 4) as usize;
+        frame.rgba[at..at + 4].try_into().unwrap()
+    }
+
+    #[test]
+    fn fake_source_draws_bars() {
+        let frame = pattern();
+        assert_eq!(frame.rgba.len(), 60 * 30 * 4);
+        assert_eq!(pixel(&frame, 0, 0), WHITE);
+        assert_eq!(pixel(&frame, 10, 0), YELLOW);
+        assert_eq!(pixel(&frame, 59, 19), RED);
+        assert_eq!(pixel(&frame, 0, 29), [0, 0, 0, 255]);
+    }
+
+    #[test]
+    fn crop_cuts_the_edges() {
+        let frame = pattern();
+        let cropped = frame.crop(Crop {
+            left: 1.0 / 6.0,
+            top: 0.1,
+            right: 0.0,
+            bottom: 1.0 / 3.0,
+        });
+        assert_eq!((cropped.width, cropped.height), (50, 17));
+        assert_eq!(cropped.rgba.len(), 50 * 17 * 4);
+        assert_eq!(pixel(&cropped, 0, 0), YELLOW);
+        assert_eq!(pixel(&cropped, 49, 16), RED);
+        assert_eq!(frame.crop(Crop::default()), frame);
+    }
+
+    #[test]
+    fn crop_keeps_a_pixel() {
+        let cropped = pattern().crop(Crop {
+            left: 0.6,
+            top: 1.0,
+            right: 0.6,
+            bottom: 0.0,
+        });
+        assert_eq!((cropped.width, cropped.height), (1, 1));
+        assert_eq!(cropped.rgba.len(), 4);
+    }
+
+    #[test]
+    fn resize_blends_neighbours() {
+        let frame = pattern();
+        assert_eq!(frame.resize(60, 30), frame);
+        let half = frame.resize(30, 15);
+        assert_eq!((half.width, half.height), (30, 15));
+        assert_eq!(half.rgba.len(), 30 * 15 * 4);
+        // Well inside a bar the color is unchanged.
+        assert_eq!(pixel(&half, 7, 2), YELLOW);
+        // A quarter of the way from the last white pixel to the first
+        // yellow one, blue has faded by a quarter.
+        let shrunk = frame.resize(40, 20);
+        assert_eq!(pixel(&shrunk, 6, 1), [255, 255, 191, 255]);
+    }
+
+    #[test]
+    fn fit_width_only_shrinks() {
+        let frame = FakeSource::default().frame();
+        assert_eq!(frame.fit_width(1280), frame);
+        let fitted = frame.fit_width(320);
+        assert_eq!((fitted.width, fitted.height), (320, 240));
+        let odd = pattern().fit_width(45);
+        assert_eq!((odd.width, odd.height), (45, 23));
+    }
+}
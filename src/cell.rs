@@ -0,0 +1,473 @@
+use crate::store::Id;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// What a column holds, which decides how its cells are parsed, shown and
+/// edited.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum CellKind {
+    Status,
+    Date,
+    Select,
+    Person,
+    LongText,
+    Files,
+    Money,
+    Outreach,
+    Timeline,
+}
+
+impl CellKind {
+    pub const ALL: [CellKind; 9] = [
+        CellKind::Status,
+        CellKind::Date,
+        CellKind::Select,
+        CellKind::Person,
+        CellKind::LongText,
+        CellKind::Files,
+        CellKind::Money,
+        CellKind::Outreach,
+        CellKind::Timeline,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            CellKind::Status => "Status",
+            CellKind::Date => "Date",
+            CellKind::Select => "Dropdown",
+            CellKind::Person => "People",
+            CellKind::LongText => "Long text",
+            CellKind::Files => "Files",
+            CellKind::Money => "Money",
+            CellKind::Outreach => "Outreach",
+            CellKind::Timeline => "Timeline",
+        }
+    }
+}
+
+/// A reference to an attached file. The file itself lives in IndexedDB under
+/// `key`; files named before attachments were stored have no key.
+#[derive(Clone, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub struct FileRef {
+    pub name: String,
+    #[serde(default)]
+    pub key: Option<String>,
+    /// MIME type, e.g. "image/png".
+    #[serde(default)]
+    pub mime: String,
+    /// Size in bytes.
+    #[serde(default)]
+    pub size: u64,
+}
+
+impl FileRef {
+    pub fn is_image(&self) -> bool {
+        self.mime.starts_with("image/")
+    }
+
+    pub fn is_pdf(&self) -> bool {
+        self.mime == "application/pdf"
+    }
+}
+
+/// Someone contacted as part of an outreach cell, and how far along they are.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct Contact {
+    /// A [`crate::people::Person`] id.
+    pub person: Id,
+    /// When they were contacted.
+    pub sent: Option<DateTime<Utc>>,
+    /// When they got back to us.
+    pub responded: Option<DateTime<Utc>>,
+}
+
+impl Contact {
+    pub fn new(person: Id) -> Self {
+        Self {
+            person,
+            sent: None,
+            responded: None,
+        }
+    }
+}
+
+/// The currency a money column is kept in. All of them count in hundredths.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Default, Serialize, Deserialize)]
+pub enum Currency {
+    #[default]
+    Usd,
+    Eur,
+    Gbp,
+    Inr,
+    Cad,
+    Aud,
+}
+
+impl Currency {
+    pub const ALL: [Currency; 6] = [
+        Currency::Usd,
+        Currency::Eur,
+        Currency::Gbp,
+        Currency::Inr,
+        Currency::Cad,
+        Currency::Aud,
+    ];
+
+    pub fn code(self) -> &'static str {
+        match self {
+            Currency::Usd => "USD",
+            Currency::Eur => "EUR",
+            Currency::Gbp => "GBP",
+            Currency::Inr => "INR",
+            Currency::Cad => "CAD",
+            Currency::Aud => "AUD",
+        }
+    }
+
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Currency::Usd => "$",
+            Currency::Eur => "€",
+            Currency::Gbp => "£",
+            Currency::Inr => "₹",
+            Currency::Cad => "CA$",
+            Currency::Aud => "A$",
+        }
+    }
+
+    /// `money` with this currency's symbol, e.g. "-€1,250.00".
+    pub fn format(self, money: Money) -> String {
+        let sign = if money.cents < 0 { "-" } else { "" };
+        format!(
+            "{}{}{}",
+            sign,
+            self.symbol(),
+            amount(money.cents.unsigned_abs())
+        )
+    }
+}
+
+/// An exact amount of money in cents. Which currency it is in is up to the
+/// column holding it.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Default, Serialize, Deserialize)]
+pub struct Money {
+    pub cents: i64,
+}
+
+impl Money {
+    /// Reads amounts like "1250", "$1,250.00" or "-EUR 3.5". Any known
+    /// currency symbol or code is accepted and dropped. Commas must split
+    /// the whole part into groups of three.
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let error = || format!("\"{}\" is not an amount like 1,250.00", input);
+        let text = input.trim();
+        let (negative, text) = match text.strip_prefix('-') {
+            Some(rest) => (true, rest.trim_start()),
+            None => (false, text),
+        };
+        let text = strip_currency(text);
+        let (whole, fraction) = text.split_once('.').unwrap_or((text, ""));
+        if whole.contains(',') && !grouped_by_thousands(whole) {
+            return Err(error());
+        }
+        let whole = whole.replace(',', "");
+        if whole.is_empty() && fraction.is_empty()
+            || !whole.chars().all(|c| c.is_ascii_digit())
+            || !fraction.chars().all(|c| c.is_ascii_digit())
+            || fraction.len() > 2
+        {
+            return Err(error());
+        }
+        let whole: i64 = if whole.is_empty() {
+            0
+        } else {
+            whole.parse().map_err(|_| error())?
+        };
+        let fraction: i64 = format!("{:0<2}", fraction).parse().map_err(|_| error())?;
+        let cents = whole
+            .checked_mul(100)
+            .and_then(|cents| cents.checked_add(fraction))
+            .ok_or_else(error)?;
+        Ok(Self {
+            cents: if negative { -cents } else { cents },
+        })
+    }
+}
+
+/// Whether `whole` reads like "1,250,000": one to three leading digits,
+/// then groups of exactly three.
+fn grouped_by_thousands(whole: &str) -> bool {
+    let mut groups = whole.split(',');
+    let first = groups.next().unwrap_or_default();
+    (1..=3).contains(&first.len()) && groups.all(|group| group.len() == 3)
+}
+
+fn strip_currency(text: &str) -> &str {
+    Currency::ALL
+        .iter()
+        .flat_map(|currency| [currency.code(), currency.symbol()])
+        .find_map(|prefix| text.strip_prefix(prefix))
+        .map(str::trim_start)
+        .unwrap_or(text)
+}
+
+impl std::ops::Add for Money {
+    /// `None` when the sum doesn't fit in an `i64` of cents.
+    type Output = Option<Money>;
+
+    fn add(self, other: Money) -> Option<Money> {
+        self.cents
+            .checked_add(other.cents)
+            .map(|cents| Money { cents })
+    }
+}
+
+impl Money {
+    /// The sum of `amounts`, or `None` if it overflows along the way.
+    pub fn total(amounts: impl IntoIterator<Item = Money>) -> Option<Money> {
+        amounts
+            .into_iter()
+            .try_fold(Money::default(), |total, money| total + money)
+    }
+}
+
+/// A count of cents without a sign, e.g. "1,250.00". Works on the unsigned
+/// magnitude so that even `i64::MIN` cents formats.
+fn amount(cents: u64) -> String {
+    let whole = (cents / 100).to_string();
+    let grouped = whole
+        .as_bytes()
+        .rchunks(3)
+        .rev()
+        .map(|chunk| std::str::from_utf8(chunk).unwrap_or_default())
+        .collect::<Vec<_>>()
+        .join(",");
+    format!("{}.{:02}", grouped, cents % 100)
+}
+
+/// The bare amount, e.g. "1,250.00" or "-3.50".
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.cents < 0 { "-" } else { "" };
+        write!(f, "{}{}", sign, amount(self.cents.unsigned_abs()))
+    }
+}
+
+/// A span of whole days, both ends included. `start` is never after `end`.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Serialize, Deserialize)]
+pub struct Timeline {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+}
+
+impl Timeline {
+    /// The span between two days, whichever order they come in.
+    pub fn new(a: NaiveDate, b: NaiveDate) -> Self {
+        Self {
+            start: a.min(b),
+            end: a.max(b),
+        }
+    }
+
+    /// How many days it covers, counting both ends.
+    pub fn days(self) -> i64 {
+        (self.end - self.start).num_days() + 1
+    }
+
+    /// Reads spans like "2024-08-11 to 2024-08-20". A single date is a span
+    /// of one day.
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let error = || format!("\"{}\" is not a range like 2024-08-11 to 2024-08-20", input);
+        let date =
+            |text: &str| NaiveDate::parse_from_str(text.trim(), DATE_FORMAT).map_err(|_| error());
+        match input.split_once(" to ").or_else(|| input.split_once('–')) {
+            Some((start, end)) => Ok(Self::new(date(start)?, date(end)?)),
+            None => {
+                let day = date(input)?;
+                Ok(Self::new(day, day))
+            }
+        }
+    }
+}
+
+/// The span as [`Timeline::parse`] reads it.
+impl fmt::Display for Timeline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} to {}",
+            self.start.format(DATE_FORMAT),
+            self.end.format(DATE_FORMAT)
+        )
+    }
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub enum CellValue {
+    /// One of the board's [`crate::status::StatusLabel`]s, by id.
+    Status(Option<Id>),
+    Date(Option<NaiveDate>),
+    Select(Option<String>),
+    /// People from the board's directory, by [`crate::people::Person`] id.
+    Person(Vec<Id>),
+    LongText(String),
+    Files(Vec<FileRef>),
+    Money(Option<Money>),
+    Outreach(Vec<Contact>),
+    Timeline(Option<Timeline>),
+}
+
+impl CellValue {
+    pub fn empty(kind: CellKind) -> Self {
+        match kind {
+            CellKind::Status => CellValue::Status(None),
+            CellKind::Date => CellValue::Date(None),
+            CellKind::Select => CellValue::Select(None),
+            CellKind::Person => CellValue::Person(Vec::new()),
+            CellKind::LongText => CellValue::LongText(String::new()),
+            CellKind::Files => CellValue::Files(Vec::new()),
+            CellKind::Money => CellValue::Money(None),
+            CellKind::Outreach => CellValue::Outreach(Vec::new()),
+            CellKind::Timeline => CellValue::Timeline(None),
+        }
+    }
+
+    pub fn kind(&self) -> CellKind {
+        match self {
+            CellValue::Status(_) => CellKind::Status,
+            CellValue::Date(_) => CellKind::Date,
+            CellValue::Select(_) => CellKind::Select,
+            CellValue::Person(_) => CellKind::Person,
+            CellValue::LongText(_) => CellKind::LongText,
+            CellValue::Files(_) => CellKind::Files,
+            CellValue::Money(_) => CellKind::Money,
+            CellValue::Outreach(_) => CellKind::Outreach,
+            CellValue::Timeline(_) => CellKind::Timeline,
+        }
+    }
+
+    /// Whether the cell has nothing filled in.
+    pub fn is_empty(&self) -> bool {
+        match self {
+            CellValue::Status(status) => status.is_none(),
+            CellValue::Date(date) => date.is_none(),
+            CellValue::Select(option) => option.is_none(),
+            CellValue::Person(people) => people.is_empty(),
+            CellValue::LongText(text) => text.trim().is_empty(),
+            CellValue::Files(files) => files.is_empty(),
+            CellValue::Money(money) => money.is_none(),
+            CellValue::Outreach(contacts) => contacts.is_empty(),
+            CellValue::Timeline(timeline) => timeline.is_none(),
+        }
+    }
+
+    /// Parses what a user typed into a cell of the given kind. Blank input
+    /// clears the cell.
+    pub fn parse(kind: CellKind, input: &str) -> Result<Self, String> {
+        let text = input.trim();
+        if text.is_empty() {
+            return Ok(Self::empty(kind));
+        }
+        Ok(match kind {
+            // Statuses and people are picked from the board's lists, never typed.
+            CellKind::Status => return Err("Pick a status from the list".to_string()),
+            CellKind::Date => CellValue::Date(Some(
+                NaiveDate::parse_from_str(text, DATE_FORMAT)
+                    .map_err(|_| format!("\"{}\" is not a date like 2024-08-11", text))?,
+            )),
+            CellKind::Select => CellValue::Select(Some(text.to_string())),
+            CellKind::Person | CellKind::Outreach => {
+                return Err("Pick people from the list".to_string())
+            }
+            CellKind::LongText => CellValue::LongText(input.to_string()),
+            CellKind::Files => CellValue::Files(
+                split_list(text)
+                    .into_iter()
+                    .map(|name| FileRef {
+                        name,
+                        ..FileRef::default()
+                    })
+                    .collect(),
+            ),
+            CellKind::Money => CellValue::Money(Some(Money::parse(text)?)),
+            CellKind::Timeline => CellValue::Timeline(Some(Timeline::parse(text)?)),
+        })
+    }
+
+    /// The text shown in the cell's editor, which [`CellValue::parse`] reads
+    /// back unchanged.
+    pub fn to_input(&self) -> String {
+        match self {
+            CellValue::Status(_) | CellValue::Person(_) | CellValue::Outreach(_) => String::new(),
+            CellValue::Date(date) => date
+                .map(|date| date.format(DATE_FORMAT).to_string())
+                .unwrap_or_default(),
+            CellValue::Select(option) => option.clone().unwrap_or_default(),
+            CellValue::LongText(text) => text.clone(),
+            CellValue::Files(files) => files
+                .iter()
+                .map(|file| file.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", "),
+            CellValue::Money(money) => money.map(|money| money.to_string()).unwrap_or_default(),
+            CellValue::Timeline(timeline) => timeline
+                .map(|timeline| timeline.to_string())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+fn split_list(text: &str) -> Vec<String> {
+    text.split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_money() {
+        let money = |cents| Money { cents };
+        assert_eq!(money(125050).to_string(), "1,250.50");
+        assert_eq!(money(-350).to_string(), "-3.50");
+        assert_eq!(Currency::Eur.format(money(-125000)), "-€1,250.00");
+        assert_eq!(Currency::Usd.format(money(5)), "$0.05");
+        assert_eq!(
+            Currency::Usd.format(money(i64::MIN)),
+            "-$92,233,720,368,547,758.08"
+        );
+    }
+
+    #[test]
+    fn sums_report_overflow() {
+        let money = |cents| Money { cents };
+        assert_eq!(money(-350) + money(125050), Some(money(124700)));
+        assert_eq!(money(i64::MIN) + money(-1), None);
+        assert_eq!(money(i64::MAX) + money(1), None);
+        assert_eq!(Money::total([]), Some(money(0)));
+        assert_eq!(Money::total([money(i64::MAX), money(1), money(-1)]), None);
+    }
+
+    #[test]
+    fn parses_money() {
+        assert_eq!(Money::parse("$1,250.5"), Ok(Money { cents: 125050 }));
+        assert_eq!(Money::parse("-EUR 3.5"), Ok(Money { cents: -350 }));
+        assert_eq!(Money::parse(".25"), Ok(Money { cents: 25 }));
+        assert_eq!(Money::parse("1,234,567"), Ok(Money { cents: 123456700 }));
+        assert!(Money::parse("1.234").is_err());
+        assert!(Money::parse("1,2,3").is_err());
+        assert!(Money::parse("12,34").is_err());
+        assert!(Money::parse("1234,567").is_err());
+        assert!(Money::parse(",250").is_err());
+        assert!(Money::parse("1,250.0,0").is_err());
+        assert!(Money::parse("ten").is_err());
+        assert!(Money::parse("99999999999999999999").is_err());
+    }
+}
@@ -0,0 +1,322 @@
+use crate::cell::{CellKind, CellValue, Currency, Money};
+use crate::editor::CellEditor;
+use crate::store::{use_board, BoardAction, Cells, Id};
+use crate::summary::Aggregate;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use web_sys::{HtmlInputElement, HtmlSelectElement, KeyboardEvent};
+use yew::prelude::*;
+
+/// Width in pixels of the leading Task/Subitem name column.
+pub const NAME_COLUMN_WIDTH: u32 = 240;
+
+const DEFAULT_COLUMN_WIDTH: u32 = 160;
+
+/// Whether a column belongs to tasks or to subitems. Each has its own list.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ColumnScope {
+    Task,
+    Subitem,
+}
+
+/// One column of a board, in display order.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct ColumnDef {
+    pub id: Id,
+    pub name: String,
+    pub kind: CellKind,
+    /// Width in pixels.
+    pub width: u32,
+    /// Value given to this column's cell on newly added items.
+    pub default: CellValue,
+    /// Choices offered by select columns.
+    #[serde(default)]
+    pub options: Vec<String>,
+    /// Currency of money columns.
+    #[serde(default)]
+    pub currency: Currency,
+    /// How group footers sum this column up, if the user picked one.
+    #[serde(default)]
+    pub summary: Option<Aggregate>,
+}
+
+impl ColumnDef {
+    pub fn new(id: Id, name: String, kind: CellKind) -> Self {
+        Self {
+            id,
+            name,
+            kind,
+            width: DEFAULT_COLUMN_WIDTH,
+            default: CellValue::empty(kind),
+            options: Vec::new(),
+            currency: Currency::default(),
+            summary: None,
+        }
+    }
+
+    /// Reads this column's cell, treating a missing one as empty.
+    pub fn cell(&self, cells: &Cells) -> CellValue {
+        cells
+            .get(&self.id)
+            .filter(|value| value.kind() == self.kind)
+            .cloned()
+            .unwrap_or_else(|| CellValue::empty(self.kind))
+    }
+}
+
+/// Cells for a new item, filled from each column's default.
+pub fn default_cells(columns: &[ColumnDef]) -> Cells {
+    columns
+        .iter()
+        .map(|column| (column.id, column.default.clone()))
+        .collect()
+}
+
+/// Inline style laying a row out as the name column followed by `columns`.
+pub fn grid_template(columns: &[ColumnDef]) -> String {
+    let widths: Vec<String> = std::iter::once(NAME_COLUMN_WIDTH)
+        .chain(columns.iter().map(|column| column.width))
+        .map(|width| format!("{}px", width))
+        .collect();
+    format!("grid-template-columns: {};", widths.join(" "))
+}
+
+/// The columns every board started with before schemas were configurable,
+/// in their original order. The people column starts with nobody picked,
+/// since the people it used to name may not be in the directory. On
+/// subitems it tracks outreach to those people.
+pub fn standard_columns(scope: ColumnScope, mut next_id: impl FnMut() -> Id) -> Vec<ColumnDef> {
+    let (area, people, people_kind, notes) = match scope {
+        ColumnScope::Task => ("Area 1", "Project Owner", CellKind::Person, "Sample Note"),
+        ColumnScope::Subitem => (
+            "Area 2",
+            "People - Sent/Responded",
+            CellKind::Outreach,
+            "Sample Subitem Note",
+        ),
+    };
+    let mut column = |name: &str, kind: CellKind, default: CellValue| ColumnDef {
+        default,
+        ..ColumnDef::new(next_id(), name.to_string(), kind)
+    };
+    vec![
+        column(
+            "Date",
+            CellKind::Date,
+            CellValue::Date(NaiveDate::from_ymd_opt(2024, 8, 11)),
+        ),
+        ColumnDef {
+            options: ["Area 1", "Area 2", "Area 3"].map(str::to_string).to_vec(),
+            ..column(
+                "Area",
+                CellKind::Select,
+                CellValue::Select(Some(area.to_string())),
+            )
+        },
+        column(people, people_kind, CellValue::empty(people_kind)),
+        column(
+            "Notes",
+            CellKind::LongText,
+            CellValue::LongText(notes.to_string()),
+        ),
+        column(
+            "Files / Image Capture",
+            CellKind::Files,
+            CellValue::Files(Vec::new()),
+        ),
+        column(
+            "Budget/Price",
+            CellKind::Money,
+            CellValue::Money(Some(Money::default())),
+        ),
+    ]
+}
+
+#[derive(Properties, PartialEq)]
+pub struct ColumnsPanelProps {
+    pub scope: ColumnScope,
+}
+
+/// Lets the user add, rename, resize, reorder and remove the task or
+/// subitem columns, and set the default each one gives new items.
+#[function_component(ColumnsPanel)]
+pub fn columns_panel(props: &ColumnsPanelProps) -> Html {
+    let board = use_board();
+    let scope = props.scope;
+    let columns = board.columns_for(scope);
+    let new_name = use_state(String::new);
+    let new_kind = use_state(|| CellKind::LongText);
+
+    let on_new_name = {
+        let new_name = new_name.clone();
+        Callback::from(move |e: InputEvent| {
+            if let Some(input) = e.target_dyn_into::<HtmlInputElement>() {
+                new_name.set(input.value());
+            }
+        })
+    };
+
+    let on_new_kind = {
+        let new_kind = new_kind.clone();
+        Callback::from(move |e: Event| {
+            if let Some(select) = e.target_dyn_into::<HtmlSelectElement>() {
+                if let Some(kind) = CellKind::ALL.get(select.selected_index() as usize) {
+                    new_kind.set(*kind);
+                }
+            }
+        })
+    };
+
+    let on_add = {
+        let board = board.clone();
+        let new_name = new_name.clone();
+        let new_kind = new_kind.clone();
+        Callback::from(move |_| {
+            let name = new_name.trim();
+            if !name.is_empty() {
+                board.dispatch(BoardAction::AddColumn {
+                    scope,
+                    name: name.to_string(),
+                    kind: *new_kind,
+                });
+                new_name.set(String::new());
+            }
+        })
+    };
+
+    let last = columns.len().saturating_sub(1);
+
+    html! {
+        <div class="mt-4 p-4 border rounded-lg bg-gray-50">
+            <span class="font-semibold">
+                { match scope {
+                    ColumnScope::Task => "Task columns",
+                    ColumnScope::Subitem => "Subitem columns",
+                } }
+            </span>
+            <ul class="mt-2 space-y-2">
+                { for columns.iter().enumerate().map(|(index, column)| {
+                    let id = column.id;
+                    let on_rename = {
+                        let board = board.clone();
+                        Callback::from(move |e: KeyboardEvent| {
+                            if e.key() == "Enter" {
+                                if let Some(input) = e.target_dyn_into::<HtmlInputElement>() {
+                                    board.dispatch(BoardAction::RenameColumn { column: id, name: input.value() });
+                                }
+                            }
+                        })
+                    };
+                    let on_width = {
+                        let board = board.clone();
+                        Callback::from(move |e: Event| {
+                            if let Some(input) = e.target_dyn_into::<HtmlInputElement>() {
+                                if let Ok(width) = input.value().parse::<u32>() {
+                                    board.dispatch(BoardAction::SetColumnWidth { column: id, width: width.max(60) });
+                                }
+                            }
+                        })
+                    };
+                    let on_default = {
+                        let board = board.clone();
+                        Callback::from(move |value: CellValue| {
+                            board.dispatch(BoardAction::SetColumnDefault { column: id, value });
+                        })
+                    };
+                    let on_options = {
+                        let board = board.clone();
+                        Callback::from(move |e: KeyboardEvent| {
+                            if e.key() == "Enter" {
+                                if let Some(input) = e.target_dyn_into::<HtmlInputElement>() {
+                                    let options = input
+                                        .value()
+                                        .split(',')
+                                        .map(str::trim)
+                                        .filter(|option| !option.is_empty())
+                                        .map(str::to_string)
+                                        .collect();
+                                    board.dispatch(BoardAction::SetColumnOptions { column: id, options });
+                                }
+                            }
+                        })
+                    };
+                    let on_currency = {
+                        let board = board.clone();
+                        Callback::from(move |e: Event| {
+                            if let Some(select) = e.target_dyn_into::<HtmlSelectElement>() {
+                                if let Some(currency) = Currency::ALL.get(select.selected_index() as usize) {
+                                    board.dispatch(BoardAction::SetColumnCurrency { column: id, currency: *currency });
+                                }
+                            }
+                        })
+                    };
+                    let on_move_up = {
+                        let board = board.clone();
+                        Callback::from(move |_| board.dispatch(BoardAction::MoveColumn { column: id, to: index.saturating_sub(1) }))
+                    };
+                    let on_move_down = {
+                        let board = board.clone();
+                        Callback::from(move |_| board.dispatch(BoardAction::MoveColumn { column: id, to: index + 1 }))
+                    };
+                    let on_remove = {
+                        let board = board.clone();
+                        Callback::from(move |_| board.dispatch(BoardAction::RemoveColumn { column: id }))
+                    };
+                    html! {
+                        <li key={id} class="flex items-center space-x-2 text-sm">
+                            <input class="border rounded p-1 w-48" type="text" value={column.name.clone()} onkeydown={on_rename} />
+                            <span class="w-20 text-gray-500">{ column.kind.label() }</span>
+                            <input class="border rounded p-1 w-20" type="number" min="60" step="10" value={column.width.to_string()} onchange={on_width} title="Width in pixels" />
+                            <span class="text-gray-500">{"Default"}</span>
+                            // Files given as a default would be shared by every new
+                            // item, so files columns always start empty.
+                            { if column.kind == CellKind::Files {
+                                html! { <span class="w-40 p-1 text-gray-400">{"No files"}</span> }
+                            } else {
+                                html! {
+                                    <div class="w-40 border rounded">
+                                        <CellEditor value={column.default.clone()} on_commit={on_default} options={column.options.clone()} currency={column.currency} />
+                                    </div>
+                                }
+                            }}
+                            { if column.kind == CellKind::Select {
+                                html! {
+                                    <input
+                                        class="border rounded p-1 w-48"
+                                        type="text"
+                                        value={column.options.join(", ")}
+                                        placeholder="Option, Option"
+                                        title="Choices, separated by commas"
+                                        onkeydown={on_options}
+                                    />
+                                }
+                            } else if column.kind == CellKind::Money {
+                                html! {
+                                    <select class="border rounded p-1" onchange={on_currency} title="Currency">
+                                        { for Currency::ALL.into_iter().map(|currency| html! {
+                                            <option selected={currency == column.currency}>{ currency.code() }</option>
+                                        }) }
+                                    </select>
+                                }
+                            } else {
+                                html! {}
+                            }}
+                            <button class="px-1 disabled:text-gray-300" disabled={index == 0} onclick={on_move_up}>{"↑"}</button>
+                            <button class="px-1 disabled:text-gray-300" disabled={index == last} onclick={on_move_down}>{"↓"}</button>
+                            <button class="text-red-600" onclick={on_remove}>{"Remove"}</button>
+                        </li>
+                    }
+                }) }
+            </ul>
+            <div class="flex items-center mt-3 space-x-2 text-sm">
+                <input class="border rounded p-1 w-48" type="text" placeholder="New column" value={(*new_name).clone()} oninput={on_new_name} />
+                <select class="border rounded p-1" onchange={on_new_kind}>
+                    { for CellKind::ALL.into_iter().map(|kind| html! {
+                        <option selected={kind == *new_kind}>{ kind.label() }</option>
+                    }) }
+                </select>
+                <button class="p-1 px-2 bg-blue-500 text-white rounded" onclick={on_add}>{"Add column"}</button>
+            </div>
+        </div>
+    }
+}
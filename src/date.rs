@@ -0,0 +1,121 @@
+use crate::cell::{CellKind, CellValue};
+use crate::column::ColumnDef;
+use crate::store::Cells;
+use chrono::{Local, NaiveDate};
+
+/// How many days ahead still counts as "due soon".
+const DUE_SOON_DAYS: i64 = 7;
+
+/// The user's current local date.
+pub fn today() -> NaiveDate {
+    Local::now().date_naive()
+}
+
+/// Describes `date` relative to `today`, e.g. "in 3 days" or "yesterday".
+/// Dates more than a month away are shown in full.
+pub fn relative(date: NaiveDate, today: NaiveDate) -> String {
+    match (date - today).num_days() {
+        0 => "today".to_string(),
+        1 => "tomorrow".to_string(),
+        -1 => "yesterday".to_string(),
+        days @ 2..=30 => format!("in {} days", days),
+        days @ -30..=-2 => format!("{} days ago", -days),
+        _ => date.format("%b %-d, %Y").to_string(),
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Due {
+    Overdue,
+    Soon,
+}
+
+impl Due {
+    pub fn of(date: NaiveDate, today: NaiveDate) -> Option<Self> {
+        match (date - today).num_days() {
+            days if days < 0 => Some(Due::Overdue),
+            days if days <= DUE_SOON_DAYS => Some(Due::Soon),
+            _ => None,
+        }
+    }
+
+    /// Background for a row in this state.
+    pub fn row_class(self) -> &'static str {
+        match self {
+            Due::Overdue => "bg-red-50",
+            Due::Soon => "bg-yellow-50",
+        }
+    }
+}
+
+/// The due state of an item, taken from the first date column in `columns`.
+pub fn due(columns: &[ColumnDef], cells: &Cells, today: NaiveDate) -> Option<Due> {
+    let column = columns
+        .iter()
+        .find(|column| column.kind == CellKind::Date)?;
+    match column.cell(cells) {
+        CellValue::Date(Some(date)) => Due::of(date, today),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Days;
+
+    /// The day `days` after Aug 11, 2024, or before it when negative.
+    /// `day(0)` stands in for today.
+    fn day(days: i64) -> NaiveDate {
+        let today = NaiveDate::from_ymd_opt(2024, 8, 11).unwrap();
+        let offset = Days::new(days.unsigned_abs());
+        if days < 0 {
+            today - offset
+        } else {
+            today + offset
+        }
+    }
+
+    #[test]
+    fn relative_names_nearby_days() {
+        assert_eq!(relative(day(-1), day(0)), "yesterday");
+        assert_eq!(relative(day(0), day(0)), "today");
+        assert_eq!(relative(day(1), day(0)), "tomorrow");
+        assert_eq!(relative(day(7), day(0)), "in 7 days");
+        assert_eq!(relative(day(8), day(0)), "in 8 days");
+        assert_eq!(relative(day(-30), day(0)), "30 days ago");
+        assert_eq!(relative(day(31), day(0)), "Sep 11, 2024");
+    }
+
+    #[test]
+    fn due_soon_covers_the_coming_week() {
+        assert_eq!(Due::of(day(-1), day(0)), Some(Due::Overdue));
+        assert_eq!(Due::of(day(0), day(0)), Some(Due::Soon));
+        assert_eq!(Due::of(day(7), day(0)), Some(Due::Soon));
+        assert_eq!(Due::of(day(8), day(0)), None);
+    }
+
+    #[test]
+    fn due_reads_the_first_date_column() {
+        let columns = [
+            ColumnDef::new(1, "Notes".to_string(), CellKind::LongText),
+            ColumnDef::new(2, "Start".to_string(), CellKind::Date),
+            ColumnDef::new(3, "End".to_string(), CellKind::Date),
+        ];
+        let cells = |start: Option<NaiveDate>| {
+            Cells::from([
+                (2, CellValue::Date(start)),
+                (3, CellValue::Date(Some(day(-5)))),
+            ])
+        };
+        assert_eq!(
+            due(&columns, &cells(Some(day(-1))), day(0)),
+            Some(Due::Overdue)
+        );
+        assert_eq!(due(&columns, &cells(Some(day(0))), day(0)), Some(Due::Soon));
+        assert_eq!(due(&columns, &cells(Some(day(7))), day(0)), Some(Due::Soon));
+        assert_eq!(due(&columns, &cells(Some(day(8))), day(0)), None);
+        assert_eq!(due(&columns, &cells(None), day(0)), None);
+        assert_eq!(due(&columns[..1], &cells(Some(day(0))), day(0)), None);
+    }
+}
@@ -0,0 +1,217 @@
+use crate::cell::{CellValue, FileRef};
+use crate::column::ColumnScope;
+use crate::editor::CellEditor;
+use crate::markdown::Markdown;
+use crate::mentions::MentionTextarea;
+use crate::store::{use_board, BoardAction, Id};
+use crate::updates::UpdatesFeed;
+use gloo_events::EventListener;
+use wasm_bindgen::JsCast;
+use web_sys::{HtmlInputElement, KeyboardEvent};
+use yew::prelude::*;
+
+/// A task or subitem, as opened in the detail panel.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ItemRef {
+    pub scope: ColumnScope,
+    pub id: Id,
+}
+
+/// Which item the detail panel shows, if any. Provided by `App`.
+pub type OpenItem = UseStateHandle<Option<ItemRef>>;
+
+#[hook]
+pub fn use_open_item() -> OpenItem {
+    use_context::<OpenItem>().expect("open item must be provided by App")
+}
+
+#[derive(Properties, PartialEq)]
+pub struct NotesEditorProps {
+    pub value: String,
+    pub on_commit: Callback<CellValue>,
+}
+
+/// Notes rendered as Markdown, with an Edit button that swaps in a textarea
+/// with @mention suggestions. Saves when the textarea loses focus.
+#[function_component(NotesEditor)]
+pub fn notes_editor(props: &NotesEditorProps) -> Html {
+    let draft = use_state(|| None::<String>);
+
+    let on_edit = {
+        let draft = draft.clone();
+        let value = props.value.clone();
+        Callback::from(move |_| draft.set(Some(value.clone())))
+    };
+
+    let on_input = {
+        let draft = draft.clone();
+        Callback::from(move |text: String| draft.set(Some(text)))
+    };
+
+    let on_blur = {
+        let draft = draft.clone();
+        let on_commit = props.on_commit.clone();
+        Callback::from(move |_: FocusEvent| {
+            if let Some(text) = (*draft).clone() {
+                on_commit.emit(CellValue::LongText(text));
+            }
+            draft.set(None);
+        })
+    };
+
+    match &*draft {
+        Some(text) => html! {
+            <MentionTextarea
+                value={text.clone()}
+                on_input={on_input}
+                onblur={on_blur}
+                rows={6}
+                placeholder="Add notes... Markdown works, and @ mentions people."
+                autofocus=true
+            />
+        },
+        None if props.value.trim().is_empty() => html! {
+            <button class="text-sm text-gray-400" onclick={on_edit}>{"Add notes"}</button>
+        },
+        None => html! {
+            <div class="flex items-start space-x-2">
+                <div class="flex-1"><Markdown text={props.value.clone()} /></div>
+                <button class="text-xs text-blue-600" onclick={on_edit}>{"Edit"}</button>
+            </div>
+        },
+    }
+}
+
+/// A panel on the right showing everything about the open item: its name,
+/// every column, and its updates.
+#[function_component(DetailPanel)]
+pub fn detail_panel() -> Html {
+    let board = use_board();
+    let open_item = use_open_item();
+
+    {
+        let open_item = open_item.clone();
+        use_effect_with((), move |_| {
+            let document = web_sys::window()
+                .and_then(|window| window.document())
+                .expect("document should be available");
+            let listener = EventListener::new(&document, "keydown", move |e| {
+                if e.dyn_ref::<KeyboardEvent>()
+                    .is_some_and(|e| e.key() == "Escape")
+                {
+                    open_item.set(None);
+                }
+            });
+            move || drop(listener)
+        });
+    }
+
+    let Some(ItemRef { scope, id }) = *open_item else {
+        return html! {};
+    };
+    let item = match scope {
+        ColumnScope::Task => board
+            .task(id)
+            .map(|task| (&task.name, &task.cells, &task.updates)),
+        ColumnScope::Subitem => board
+            .subitem(id)
+            .map(|subitem| (&subitem.name, &subitem.cells, &subitem.updates)),
+    };
+    let Some((name, cells, updates)) = item else {
+        return html! {};
+    };
+
+    let on_close = {
+        let open_item = open_item.clone();
+        Callback::from(move |_| open_item.set(None))
+    };
+
+    let on_rename = {
+        let board = board.clone();
+        Callback::from(move |e: KeyboardEvent| {
+            if e.key() == "Enter" {
+                if let Some(input) = e.target_dyn_into::<HtmlInputElement>() {
+                    let name = input.value();
+                    board.dispatch(match scope {
+                        ColumnScope::Task => BoardAction::RenameTask { task: id, name },
+                        ColumnScope::Subitem => BoardAction::RenameSubitem { subitem: id, name },
+                    });
+                }
+            }
+        })
+    };
+
+    let on_cell_commit = |column: Id| {
+        let board = board.clone();
+        Callback::from(move |value: CellValue| {
+            board.dispatch(match scope {
+                ColumnScope::Task => BoardAction::UpdateTask {
+                    task: id,
+                    column,
+                    value,
+                },
+                ColumnScope::Subitem => BoardAction::UpdateSubitem {
+                    subitem: id,
+                    column,
+                    value,
+                },
+            });
+        })
+    };
+
+    let on_cell_attach = |column: Id| {
+        let board = board.clone();
+        Callback::from(move |files: Vec<FileRef>| {
+            board.dispatch(BoardAction::AttachFiles {
+                scope,
+                item: id,
+                column: Some(column),
+                files,
+            });
+        })
+    };
+
+    html! {
+        <aside class="fixed inset-y-0 right-0 z-10 flex flex-col w-1/3 min-w-96 bg-white border-l shadow-xl">
+            <div class="flex items-center p-4 border-b">
+                <input
+                    key={id}
+                    class="flex-1 text-xl font-semibold border-none focus:ring-0"
+                    type="text"
+                    value={name.clone()}
+                    onkeydown={on_rename}
+                />
+                <button class="px-2" onclick={on_close} title="Close (Esc)">{"✕"}</button>
+            </div>
+            <div class="flex-1 p-4 space-y-6 overflow-y-auto">
+                <dl key={id} class="space-y-3">
+                    { for board.columns_for(scope).iter().map(|column| html! {
+                        <div key={column.id} class="flex flex-col space-y-1">
+                            <dt class="text-xs font-semibold text-gray-500 uppercase">{ &column.name }</dt>
+                            <dd>
+                                { match column.cell(cells) {
+                                    CellValue::LongText(text) => html! {
+                                        <NotesEditor value={text} on_commit={on_cell_commit(column.id)} />
+                                    },
+                                    value => html! {
+                                        <CellEditor
+                                            value={value}
+                                            on_commit={on_cell_commit(column.id)}
+                                            on_attach={on_cell_attach(column.id)}
+                                            options={column.options.clone()}
+                                            currency={column.currency}
+                                        />
+                                    },
+                                }}
+                            </dd>
+                        </div>
+                    }) }
+                </dl>
+                <div>
+                    <h2 class="mb-2 font-semibold">{"Updates"}</h2>
+                    <UpdatesFeed key={id} scope={scope} item={id} updates={updates.clone()} />
+                </div>
+            </div>
+        </aside>
+    }
+}
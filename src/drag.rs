@@ -0,0 +1,24 @@
+use crate::store::Id;
+use wasm_bindgen::JsCast;
+use web_sys::{DragEvent, Node};
+
+/// Starts dragging the item `id`. The views keep what is being dragged in
+/// their own state; this only fills in the drag data.
+pub fn start(e: &DragEvent, id: Id) {
+    // Firefox only starts a drag when something is set.
+    if let Some(data) = e.data_transfer() {
+        let _ = data.set_data("text/plain", &id.to_string());
+    }
+}
+
+/// Whether a dragleave took the pointer out of the drop target listening for
+/// it, rather than onto one of its children.
+pub fn left(e: &DragEvent) -> bool {
+    let target = e
+        .current_target()
+        .and_then(|target| target.dyn_into::<Node>().ok());
+    let to = e
+        .related_target()
+        .and_then(|target| target.dyn_into::<Node>().ok());
+    !target.is_some_and(|target| target.contains(to.as_ref()))
+}
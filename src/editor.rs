@@ -0,0 +1,237 @@
+use crate::cell::{CellKind, CellValue, Currency, FileRef, Timeline};
+use crate::date::{relative, today};
+use crate::files::FilesCell;
+use crate::markdown::plain_text;
+use crate::outreach::OutreachCell;
+use crate::people::PersonPicker;
+use crate::status::StatusPicker;
+use chrono::NaiveDate;
+use web_sys::{HtmlInputElement, HtmlSelectElement, KeyboardEvent};
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct CellEditorProps {
+    pub value: CellValue,
+    pub on_commit: Callback<CellValue>,
+    /// Choices offered by select cells.
+    #[prop_or_default]
+    pub options: Vec<String>,
+    /// Currency shown in front of money cells.
+    #[prop_or_default]
+    pub currency: Currency,
+    /// Opens the item's detail panel. When set, long text shows a one-line
+    /// preview that opens the panel instead of an input.
+    #[prop_or_default]
+    pub on_open: Option<Callback<()>>,
+    /// Adds uploaded files to a files cell as it is when they finish. Without
+    /// it they are committed along with the files shown when the upload began.
+    #[prop_or_default]
+    pub on_attach: Option<Callback<Vec<FileRef>>>,
+    #[prop_or_default]
+    pub class: Classes,
+}
+
+/// Edits one typed cell. Text-based kinds commit on Enter and show a parse
+/// error under the field instead of saving a bad value.
+#[function_component(CellEditor)]
+pub fn cell_editor(props: &CellEditorProps) -> Html {
+    let error = use_state(|| None::<String>);
+    let kind = props.value.kind();
+
+    if let CellValue::Status(value) = props.value {
+        return html! { <StatusPicker value={value} on_commit={props.on_commit.clone()} /> };
+    }
+
+    if let CellValue::Person(people) = &props.value {
+        return html! { <PersonPicker value={people.clone()} on_commit={props.on_commit.clone()} /> };
+    }
+
+    if let CellValue::Outreach(contacts) = &props.value {
+        return html! { <OutreachCell value={contacts.clone()} on_commit={props.on_commit.clone()} /> };
+    }
+
+    if let CellValue::Files(files) = &props.value {
+        let on_attach = props.on_attach.clone().unwrap_or_else(|| {
+            let files = files.clone();
+            props.on_commit.reform(move |added: Vec<FileRef>| {
+                CellValue::Files(files.iter().cloned().chain(added).collect())
+            })
+        });
+        return html! { <FilesCell value={files.clone()} on_commit={props.on_commit.clone()} on_attach={on_attach} /> };
+    }
+
+    if let (CellValue::LongText(text), Some(on_open)) = (&props.value, &props.on_open) {
+        let on_click = {
+            let on_open = on_open.clone();
+            Callback::from(move |_| on_open.emit(()))
+        };
+        let preview = plain_text(text);
+        return html! {
+            <button class={classes!("w-full", "px-2", "text-sm", "text-left", "truncate", preview.is_empty().then_some("text-gray-400"), props.class.clone())} onclick={on_click} title="Open notes">
+                { if preview.is_empty() { "Add notes".to_string() } else { preview } }
+            </button>
+        };
+    }
+
+    if kind == CellKind::Select {
+        let selected = props.value.to_input();
+        let on_change = {
+            let on_commit = props.on_commit.clone();
+            Callback::from(move |e: Event| {
+                if let Some(select) = e.target_dyn_into::<HtmlSelectElement>() {
+                    if let Ok(value) = CellValue::parse(kind, &select.value()) {
+                        on_commit.emit(value);
+                    }
+                }
+            })
+        };
+        return html! {
+            <select class={classes!("text-sm", "border-none", "focus:ring-0", "bg-transparent", props.class.clone())} onchange={on_change}>
+                <option value="" selected={selected.is_empty()}>{"-"}</option>
+                { for props.options.iter().map(|option| html! {
+                    <option value={option.clone()} selected={*option == selected}>{ option }</option>
+                }) }
+            </select>
+        };
+    }
+
+    if let CellValue::Date(date) = props.value {
+        let on_change = {
+            let error = error.clone();
+            let on_commit = props.on_commit.clone();
+            Callback::from(move |e: Event| {
+                if let Some(input) = e.target_dyn_into::<HtmlInputElement>() {
+                    match CellValue::parse(kind, &input.value()) {
+                        Ok(value) => {
+                            error.set(None);
+                            on_commit.emit(value);
+                        }
+                        Err(message) => error.set(Some(message)),
+                    }
+                }
+            })
+        };
+        return html! {
+            <div class="flex flex-col">
+                <input
+                    class={classes!("text-sm", "border-none", "focus:ring-0", "bg-transparent", props.class.clone())}
+                    type="date"
+                    value={props.value.to_input()}
+                    onchange={on_change}
+                />
+                { if let Some(message) = &*error {
+                    html! { <span class="text-xs text-red-600">{ message }</span> }
+                } else if let Some(date) = date {
+                    html! { <span class="text-xs text-gray-500">{ relative(date, today()) }</span> }
+                } else {
+                    html! {}
+                }}
+            </div>
+        };
+    }
+
+    if let CellValue::Timeline(timeline) = props.value {
+        let (start, end) = match timeline {
+            Some(timeline) => (Some(timeline.start), Some(timeline.end)),
+            None => (None, None),
+        };
+        // Setting one end of an empty span makes a one-day span, and clearing
+        // either end clears the cell.
+        let on_change = |set_start: bool| {
+            let on_commit = props.on_commit.clone();
+            Callback::from(move |e: Event| {
+                let Some(input) = e.target_dyn_into::<HtmlInputElement>() else {
+                    return;
+                };
+                let value = NaiveDate::parse_from_str(&input.value(), "%Y-%m-%d")
+                    .ok()
+                    .map(|day| {
+                        let other = if set_start { end } else { start };
+                        let other = other.unwrap_or(day);
+                        Timeline::new(day, other)
+                    });
+                on_commit.emit(CellValue::Timeline(value));
+            })
+        };
+        let input = |day: Option<NaiveDate>, set_start: bool| {
+            html! {
+                <input
+                    class={classes!("text-sm", "border-none", "focus:ring-0", "bg-transparent", "w-32", props.class.clone())}
+                    type="date"
+                    value={day.map(|day| day.format("%Y-%m-%d").to_string()).unwrap_or_default()}
+                    max={if set_start { end.map(|day| day.format("%Y-%m-%d").to_string()) } else { None }}
+                    min={if set_start { None } else { start.map(|day| day.format("%Y-%m-%d").to_string()) }}
+                    onchange={on_change(set_start)}
+                />
+            }
+        };
+        return html! {
+            <div class="flex flex-col">
+                <div class="flex items-center">
+                    { input(start, true) }
+                    <span class="text-sm text-gray-400">{"–"}</span>
+                    { input(end, false) }
+                </div>
+                { if let Some(timeline) = timeline {
+                    html! {
+                        <span class="text-xs text-gray-500">
+                            { match timeline.days() {
+                                1 => "1 day".to_string(),
+                                days => format!("{} days", days),
+                            } }
+                        </span>
+                    }
+                } else {
+                    html! {}
+                }}
+            </div>
+        };
+    }
+
+    let on_keydown = {
+        let error = error.clone();
+        let on_commit = props.on_commit.clone();
+        Callback::from(move |e: KeyboardEvent| {
+            if e.key() == "Enter" {
+                if let Some(input) = e.target_dyn_into::<HtmlInputElement>() {
+                    match CellValue::parse(kind, &input.value()) {
+                        Ok(value) => {
+                            error.set(None);
+                            on_commit.emit(value);
+                        }
+                        Err(message) => error.set(Some(message)),
+                    }
+                }
+            }
+        })
+    };
+
+    let placeholder = match kind {
+        CellKind::Money => "0.00",
+        _ => "",
+    };
+
+    html! {
+        <div class="flex flex-col">
+            <div class="flex items-center">
+                { if kind == CellKind::Money {
+                    html! { <span class="text-sm text-gray-500">{ props.currency.symbol() }</span> }
+                } else {
+                    html! {}
+                }}
+                <input
+                    class={classes!("text-sm", "border-none", "focus:ring-0", "focus:border-blue-300", "w-full", error.is_some().then_some("text-red-600"), props.class.clone())}
+                    type="text"
+                    value={props.value.to_input()}
+                    placeholder={placeholder}
+                    onkeydown={on_keydown}
+                />
+            </div>
+            { if let Some(message) = &*error {
+                html! { <span class="text-xs text-red-600">{ message }</span> }
+            } else {
+                html! {}
+            }}
+        </div>
+    }
+}
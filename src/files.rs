@@ -0,0 +1,338 @@
+use crate::attachments;
+use crate::cell::{CellValue, FileRef};
+use gloo_console::log;
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::spawn_local;
+use web_sys::{ClipboardEvent, DragEvent, File, FileList, HtmlInputElement, Url};
+use yew::prelude::*;
+
+/// How many thumbnails a files cell shows before "+N".
+const THUMBNAILS: usize = 3;
+
+/// e.g. "820 B", "14.2 KB" or "3.1 MB".
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+fn file_list(files: Option<FileList>) -> Vec<File> {
+    files
+        .map(|files| (0..files.length()).filter_map(|i| files.get(i)).collect())
+        .unwrap_or_default()
+}
+
+/// An object URL for a stored file, or `None` while it loads or if it is
+/// gone. The URL is revoked when the key changes or the component goes away.
+#[hook]
+pub fn use_object_url(key: Option<String>) -> Option<String> {
+    let url = use_state_eq(|| None::<String>);
+    {
+        let url = url.clone();
+        use_effect_with(key, move |key| {
+            let alive = Rc::new(Cell::new(true));
+            let created = Rc::new(RefCell::new(None::<String>));
+            url.set(None);
+            if let Some(key) = key.clone() {
+                let alive = alive.clone();
+                let created = created.clone();
+                spawn_local(async move {
+                    match attachments::get(&key).await {
+                        Ok(Some(blob)) if alive.get() => {
+                            if let Ok(object_url) = Url::create_object_url_with_blob(&blob) {
+                                *created.borrow_mut() = Some(object_url.clone());
+                                url.set(Some(object_url));
+                            }
+                        }
+                        Ok(_) => {}
+                        Err(err) => log!("Could not load attachment:", err),
+                    }
+                });
+            }
+            move || {
+                alive.set(false);
+                if let Some(object_url) = created.borrow_mut().take() {
+                    let _ = Url::revoke_object_url(&object_url);
+                }
+            }
+        });
+    }
+    (*url).clone()
+}
+
+#[derive(Properties, PartialEq)]
+pub struct ThumbnailProps {
+    pub file: FileRef,
+    pub onclick: Callback<MouseEvent>,
+}
+
+/// A small preview of an image, or the file's extension for anything else.
+#[function_component(Thumbnail)]
+pub fn thumbnail(props: &ThumbnailProps) -> Html {
+    let url = use_object_url(
+        props
+            .file
+            .is_image()
+            .then(|| props.file.key.clone())
+            .flatten(),
+    );
+    let extension = props
+        .file
+        .name
+        .rsplit_once('.')
+        .map(|(_, extension)| extension.to_uppercase())
+        .unwrap_or_else(|| "FILE".to_string());
+
+    html! {
+        <button class="w-8 h-8 overflow-hidden rounded border bg-gray-100 text-[9px] font-semibold text-gray-600" title={props.file.name.clone()} onclick={props.onclick.clone()}>
+            { if let Some(url) = url {
+                html! { <img class="w-full h-full object-cover" src={url} alt={props.file.name.clone()} /> }
+            } else {
+                html! { { extension } }
+            }}
+        </button>
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct GalleryProps {
+    pub files: Vec<FileRef>,
+    pub index: usize,
+    pub on_show: Callback<usize>,
+    pub on_remove: Callback<usize>,
+    pub on_close: Callback<()>,
+}
+
+/// A full-size preview of one file, with arrows to step through the rest.
+/// Images and PDFs are shown inline; anything else can be downloaded.
+#[function_component(Gallery)]
+pub fn gallery(props: &GalleryProps) -> Html {
+    let file = props.files.get(props.index).cloned().unwrap_or_default();
+    let url = use_object_url(file.key.clone());
+    let index = props.index;
+    let last = props.files.len().saturating_sub(1);
+
+    let on_close = {
+        let on_close = props.on_close.clone();
+        Callback::from(move |_| on_close.emit(()))
+    };
+    let on_previous = {
+        let on_show = props.on_show.clone();
+        Callback::from(move |_| on_show.emit(index.saturating_sub(1)))
+    };
+    let on_next = {
+        let on_show = props.on_show.clone();
+        Callback::from(move |_| on_show.emit((index + 1).min(last)))
+    };
+    let on_remove = {
+        let on_remove = props.on_remove.clone();
+        Callback::from(move |_| on_remove.emit(index))
+    };
+
+    html! {
+        <div class="fixed inset-0 z-20 flex items-center justify-center bg-black bg-opacity-60">
+            <div class="flex flex-col w-3/4 max-h-screen p-4 bg-white rounded-lg shadow-lg">
+                <div class="flex items-center mb-3 space-x-2 text-sm">
+                    <span class="flex-1 font-semibold truncate">{ &file.name }</span>
+                    <span class="text-gray-500">{ format_size(file.size) }</span>
+                    <span class="text-gray-500">{ format!("{} of {}", index + 1, props.files.len()) }</span>
+                    <button class="px-2 border rounded disabled:text-gray-300" disabled={index == 0} onclick={on_previous}>{"‹"}</button>
+                    <button class="px-2 border rounded disabled:text-gray-300" disabled={index == last} onclick={on_next}>{"›"}</button>
+                    <button class="px-2 text-red-600" onclick={on_remove}>{"Remove"}</button>
+                    <button class="px-2" onclick={on_close}>{"✕"}</button>
+                </div>
+                { match url {
+                    Some(url) if file.is_image() => html! {
+                        <img class="object-contain max-h-[75vh]" src={url} alt={file.name.clone()} />
+                    },
+                    Some(url) if file.is_pdf() => html! {
+                        <iframe class="w-full h-[75vh]" src={url} title={file.name.clone()} />
+                    },
+                    Some(url) => html! {
+                        <p class="text-sm text-gray-600">
+                            {"No preview for this file. "}
+                            <a class="text-blue-600 underline" href={url} download={file.name.clone()}>{"Download"}</a>
+                        </p>
+                    },
+                    None if file.key.is_none() => html! {
+                        <p class="text-sm text-gray-600">{"This file was only named, never uploaded."}</p>
+                    },
+                    None => html! { <p class="text-sm text-gray-600">{"Loading..."}</p> },
+                }}
+            </div>
+        </div>
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct FilesCellProps {
+    pub value: Vec<FileRef>,
+    pub on_commit: Callback<CellValue>,
+    /// Adds files once they have uploaded, to the cell as it is by then.
+    pub on_attach: Callback<Vec<FileRef>>,
+}
+
+/// A files cell: how many files are attached and thumbnails of the first few.
+/// Files can be added with the picker, by dropping them on the cell or by
+/// pasting into it, and open in a gallery when clicked.
+#[function_component(FilesCell)]
+pub fn files_cell(props: &FilesCellProps) -> Html {
+    let gallery = use_state(|| None::<usize>);
+    let is_dragging = use_state(|| false);
+    let uploading = use_state(|| 0usize);
+
+    let attach = {
+        let on_attach = props.on_attach.clone();
+        let uploading = uploading.clone();
+        Callback::from(move |added: Vec<File>| {
+            if added.is_empty() {
+                return;
+            }
+            let on_attach = on_attach.clone();
+            let uploading = uploading.clone();
+            uploading.set(added.len());
+            spawn_local(async move {
+                let mut files = Vec::new();
+                for file in &added {
+                    match attachments::put(file).await {
+                        Ok(file) => files.push(file),
+                        Err(err) => log!(format!("Could not attach {}:", file.name()), err),
+                    }
+                }
+                uploading.set(0);
+                if !files.is_empty() {
+                    on_attach.emit(files);
+                }
+            });
+        })
+    };
+
+    let on_pick = {
+        let attach = attach.clone();
+        Callback::from(move |e: Event| {
+            if let Some(input) = e.target_dyn_into::<HtmlInputElement>() {
+                attach.emit(file_list(input.files()));
+                input.set_value("");
+            }
+        })
+    };
+
+    let on_drag_over = {
+        let is_dragging = is_dragging.clone();
+        Callback::from(move |e: DragEvent| {
+            // Without this the browser opens the file instead of dropping it.
+            e.prevent_default();
+            is_dragging.set(true);
+        })
+    };
+
+    let on_drag_leave = {
+        let is_dragging = is_dragging.clone();
+        Callback::from(move |_: DragEvent| is_dragging.set(false))
+    };
+
+    let on_drop = {
+        let attach = attach.clone();
+        let is_dragging = is_dragging.clone();
+        Callback::from(move |e: DragEvent| {
+            e.prevent_default();
+            is_dragging.set(false);
+            attach.emit(file_list(e.data_transfer().and_then(|data| data.files())));
+        })
+    };
+
+    let on_paste = {
+        let attach = attach.clone();
+        Callback::from(move |e: Event| {
+            let Some(e) = e.dyn_ref::<ClipboardEvent>() else {
+                return;
+            };
+            let files = file_list(e.clipboard_data().and_then(|data| data.files()));
+            if !files.is_empty() {
+                e.prevent_default();
+                attach.emit(files);
+            }
+        })
+    };
+
+    let open = |index: usize| {
+        let gallery = gallery.clone();
+        Callback::from(move |_| gallery.set(Some(index)))
+    };
+
+    let on_show = {
+        let gallery = gallery.clone();
+        Callback::from(move |index: usize| gallery.set(Some(index)))
+    };
+
+    let on_close = {
+        let gallery = gallery.clone();
+        Callback::from(move |_| gallery.set(None))
+    };
+
+    let on_remove = {
+        let files = props.value.clone();
+        let on_commit = props.on_commit.clone();
+        let gallery = gallery.clone();
+        Callback::from(move |index: usize| {
+            let mut files = files.clone();
+            if index < files.len() {
+                files.remove(index);
+            }
+            gallery.set(index.checked_sub(1).or((!files.is_empty()).then_some(0)));
+            on_commit.emit(CellValue::Files(files));
+        })
+    };
+
+    let count = props.value.len();
+
+    html! {
+        <div
+            class={classes!("flex", "items-center", "space-x-1", "min-h-8", "px-1", "rounded", "focus:outline-none", "focus:ring-1", "focus:ring-blue-300", is_dragging.then_some("bg-blue-50"))}
+            tabindex="0"
+            title="Drop or paste files here"
+            ondragover={on_drag_over}
+            ondragleave={on_drag_leave}
+            ondrop={on_drop}
+            onpaste={on_paste}
+        >
+            { for props.value.iter().take(THUMBNAILS).enumerate().map(|(index, file)| html! {
+                <Thumbnail file={file.clone()} onclick={open(index)} />
+            }) }
+            { if count > THUMBNAILS {
+                html! { <button class="text-xs text-gray-500" onclick={open(THUMBNAILS)}>{ format!("+{}", count - THUMBNAILS) }</button> }
+            } else {
+                html! {}
+            }}
+            <span class="text-sm text-gray-500">
+                { match (*uploading, count) {
+                    (0, 0) => "No files".to_string(),
+                    (0, 1) => "1 file".to_string(),
+                    (0, count) => format!("{} files", count),
+                    (uploading, _) => format!("Uploading {}...", uploading),
+                } }
+            </span>
+            <label class="px-1 text-gray-500 cursor-pointer hover:text-blue-600" title="Attach files">
+                {"+"}
+                <input class="hidden" type="file" multiple=true onchange={on_pick} />
+            </label>
+            { match *gallery {
+                Some(index) if index < count => html! {
+                    <Gallery files={props.value.clone()} index={index} on_show={on_show} on_remove={on_remove} on_close={on_close} />
+                },
+                _ => html! {},
+            }}
+        </div>
+    }
+}
@@ -0,0 +1,393 @@
+use crate::cell::CellValue;
+use crate::column::ColumnDef;
+use crate::people::is_assigned;
+use crate::rules::Rules;
+use crate::store::{Board, Cells, Id, SubitemData, TaskData};
+use web_sys::{HtmlInputElement, KeyboardEvent};
+use yew::prelude::*;
+
+/// How much text a match snippet shows on either side of the match.
+const SNIPPET_CONTEXT: usize = 30;
+
+/// What the board is narrowed to. Set from the toolbar in `App` and passed
+/// down to every group.
+#[derive(Clone, PartialEq, Default, Debug)]
+pub struct BoardFilter {
+    /// Only tasks assigned to this person, directly or on a subitem.
+    pub assignee: Option<Id>,
+    /// Only tasks and subitems whose name or cells contain this text, in any
+    /// case.
+    pub text: String,
+    /// Column conditions from the filter builder.
+    pub rules: Rules,
+}
+
+/// A cell as the text it shows, so it can be searched: label and people
+/// names rather than ids.
+pub fn cell_text(board: &Board, value: &CellValue) -> String {
+    let names = |people: &mut dyn Iterator<Item = Id>| {
+        people
+            .filter_map(|person| board.person(person))
+            .map(|person| person.name.as_str())
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    match value {
+        CellValue::Status(status) => status
+            .and_then(|status| board.status_labels.iter().find(|label| label.id == status))
+            .map(|label| label.name.clone())
+            .unwrap_or_default(),
+        CellValue::Person(people) => names(&mut people.iter().copied()),
+        CellValue::Outreach(contacts) => names(&mut contacts.iter().map(|contact| contact.person)),
+        value => value.to_input(),
+    }
+}
+
+/// Byte ranges of every case-insensitive occurrence of `query` in `text`.
+pub fn find_all(text: &str, query: &str) -> Vec<(usize, usize)> {
+    let query: Vec<char> = query.chars().flat_map(char::to_lowercase).collect();
+    if query.is_empty() {
+        return Vec::new();
+    }
+    // Lowercase character by character so the offsets stay valid in `text`
+    // even when lowercasing changes a character's length.
+    let chars: Vec<(usize, Vec<char>)> = text
+        .char_indices()
+        .map(|(offset, c)| (offset, c.to_lowercase().collect()))
+        .collect();
+    let mut found = Vec::new();
+    let mut start = 0;
+    while start < chars.len() {
+        let mut matched = 0;
+        let mut end = start;
+        while matched < query.len() && end < chars.len() {
+            let lower = &chars[end].1;
+            if query.get(matched..matched + lower.len()) != Some(lower.as_slice()) {
+                break;
+            }
+            matched += lower.len();
+            end += 1;
+        }
+        if matched == query.len() {
+            let to = chars.get(end).map_or(text.len(), |(offset, _)| *offset);
+            found.push((chars[start].0, to));
+            start = end;
+        } else {
+            start += 1;
+        }
+    }
+    found
+}
+
+impl BoardFilter {
+    pub fn is_searching(&self) -> bool {
+        !self.text.trim().is_empty()
+    }
+
+    fn query(&self) -> &str {
+        self.text.trim()
+    }
+
+    fn item_matches(
+        &self,
+        board: &Board,
+        name: &str,
+        columns: &[ColumnDef],
+        cells: &Cells,
+    ) -> bool {
+        !find_all(name, self.query()).is_empty()
+            || !self.matching_cells(board, columns, cells).is_empty()
+    }
+
+    /// Whether the task's own name or cells match the search.
+    pub fn task_matches(&self, board: &Board, task: &TaskData) -> bool {
+        self.is_searching() && self.item_matches(board, &task.name, &board.columns, &task.cells)
+    }
+
+    /// Whether the subitem's own name or cells match the search.
+    pub fn subitem_matches(&self, board: &Board, subitem: &SubitemData) -> bool {
+        self.is_searching()
+            && self.item_matches(board, &subitem.name, &board.subitem_columns, &subitem.cells)
+    }
+
+    /// Whether any of the task's subitems match the search, so it should open
+    /// to show them.
+    pub fn has_subitem_matches(&self, board: &Board, task: &TaskData) -> bool {
+        task.subitems
+            .iter()
+            .any(|subitem| self.subitem_matches(board, subitem))
+    }
+
+    pub fn shows_task(&self, board: &Board, task: &TaskData) -> bool {
+        let assigned = match self.assignee {
+            Some(person) => {
+                is_assigned(&board.columns, &task.cells, person)
+                    || task
+                        .subitems
+                        .iter()
+                        .any(|subitem| is_assigned(&board.subitem_columns, &subitem.cells, person))
+            }
+            None => true,
+        };
+        let found = !self.is_searching()
+            || self.task_matches(board, task)
+            || self.has_subitem_matches(board, task);
+        assigned && found && self.rules.shows_task(board, task)
+    }
+
+    /// Whether the task should open by itself to show the subitems that
+    /// matched the search or passed the rules.
+    pub fn opens_task(&self, board: &Board, task: &TaskData) -> bool {
+        self.has_subitem_matches(board, task)
+            || self.rules.uses_subitems(board)
+                && task
+                    .subitems
+                    .iter()
+                    .any(|subitem| self.rules.shows_subitem(board, task, subitem))
+    }
+
+    /// The task's subitems to list: the ones that pass the rules and match
+    /// the search. A step that would leave none, because the task passed on
+    /// its own, is skipped.
+    pub fn visible_subitems<'a>(&self, board: &Board, task: &'a TaskData) -> Vec<&'a SubitemData> {
+        let mut subitems: Vec<&SubitemData> = task.subitems.iter().collect();
+        if self.rules.uses_subitems(board) {
+            let passing: Vec<_> = subitems
+                .iter()
+                .copied()
+                .filter(|subitem| self.rules.shows_subitem(board, task, subitem))
+                .collect();
+            if !passing.is_empty() {
+                subitems = passing;
+            }
+        }
+        if self.is_searching() {
+            let matching: Vec<_> = subitems
+                .iter()
+                .copied()
+                .filter(|subitem| self.subitem_matches(board, subitem))
+                .collect();
+            if !matching.is_empty() {
+                subitems = matching;
+            }
+        }
+        subitems
+    }
+
+    pub fn visible_tasks<'a>(
+        &self,
+        board: &Board,
+        tasks: impl IntoIterator<Item = &'a TaskData>,
+    ) -> Vec<&'a TaskData> {
+        tasks
+            .into_iter()
+            .filter(|task| self.shows_task(board, task))
+            .collect()
+    }
+
+    /// How many tasks and subitems match the search, and in how many groups.
+    pub fn results(&self, board: &Board) -> (usize, usize) {
+        let mut results = 0;
+        let mut groups = 0;
+        for group in &board.groups {
+            let mut found = 0;
+            for task in self.visible_tasks(board, &group.tasks) {
+                found += usize::from(self.task_matches(board, task));
+                found += task
+                    .subitems
+                    .iter()
+                    .filter(|subitem| self.subitem_matches(board, subitem))
+                    .count();
+            }
+            results += found;
+            groups += usize::from(found > 0);
+        }
+        (results, groups)
+    }
+
+    /// The item's matching cells as (column name, cell text), for showing
+    /// why it matched.
+    pub fn matching_cells(
+        &self,
+        board: &Board,
+        columns: &[ColumnDef],
+        cells: &Cells,
+    ) -> Vec<(String, String)> {
+        if !self.is_searching() {
+            return Vec::new();
+        }
+        columns
+            .iter()
+            .map(|column| (column.name.clone(), cell_text(board, &column.cell(cells))))
+            .filter(|(_, text)| !find_all(text, self.query()).is_empty())
+            .collect()
+    }
+}
+
+/// Cuts `text` down to the area around its first match of `query`, on one
+/// line.
+fn snippet(text: &str, query: &str) -> String {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    let Some(&(start, end)) = find_all(&text, query).first() else {
+        return text;
+    };
+    let from = text[..start]
+        .char_indices()
+        .rev()
+        .nth(SNIPPET_CONTEXT - 1)
+        .map_or(0, |(offset, _)| offset);
+    let to = text[end..]
+        .char_indices()
+        .nth(SNIPPET_CONTEXT * 2)
+        .map_or(text.len(), |(offset, _)| end + offset);
+    format!(
+        "{}{}{}",
+        if from > 0 { "…" } else { "" },
+        &text[from..to],
+        if to < text.len() { "…" } else { "" },
+    )
+}
+
+#[derive(Properties, PartialEq)]
+pub struct HighlightProps {
+    pub text: String,
+    pub query: String,
+}
+
+/// `text` with every match of `query` marked.
+#[function_component(Highlight)]
+pub fn highlight(props: &HighlightProps) -> Html {
+    let text = &props.text;
+    let mut parts = Vec::new();
+    let mut last = 0;
+    for (start, end) in find_all(text, props.query.trim()) {
+        parts.push(html! { { &text[last..start] } });
+        parts.push(html! { <mark class="bg-yellow-200 rounded-sm">{ &text[start..end] }</mark> });
+        last = end;
+    }
+    parts.push(html! { { &text[last..] } });
+
+    html! { <>{ for parts }</> }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct MatchesProps {
+    /// (column name, cell text) pairs from [`BoardFilter::matching_cells`].
+    pub cells: Vec<(String, String)>,
+    pub query: String,
+}
+
+/// A line under a row listing the cells that matched the search.
+#[function_component(Matches)]
+pub fn matches(props: &MatchesProps) -> Html {
+    if props.cells.is_empty() {
+        return html! {};
+    }
+
+    html! {
+        <div class="flex flex-wrap col-span-full gap-x-4 mt-1 ml-8 text-xs text-gray-600">
+            { for props.cells.iter().map(|(column, text)| html! {
+                <span>
+                    <span class="mr-1 font-semibold">{ format!("{}:", column) }</span>
+                    <Highlight text={snippet(text, props.query.trim())} query={props.query.clone()} />
+                </span>
+            }) }
+        </div>
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct NameInputProps {
+    pub name: String,
+    pub query: String,
+    pub on_rename: Callback<String>,
+    #[prop_or_default]
+    pub class: Classes,
+}
+
+/// A task or subitem name, renamed on Enter. While the name matches the
+/// search it shows with the match marked, and turns into an input on click.
+#[function_component(NameInput)]
+pub fn name_input(props: &NameInputProps) -> Html {
+    let is_editing = use_state(|| false);
+
+    let on_keydown = {
+        let on_rename = props.on_rename.clone();
+        let is_editing = is_editing.clone();
+        Callback::from(move |e: KeyboardEvent| {
+            if e.key() == "Enter" {
+                if let Some(input) = e.target_dyn_into::<HtmlInputElement>() {
+                    on_rename.emit(input.value());
+                    is_editing.set(false);
+                }
+            }
+        })
+    };
+
+    if !*is_editing && !find_all(&props.name, props.query.trim()).is_empty() {
+        let on_edit = {
+            let is_editing = is_editing.clone();
+            Callback::from(move |_| is_editing.set(true))
+        };
+        return html! {
+            <span class={classes!("px-3", "py-2", "cursor-text", props.class.clone())} onclick={on_edit}>
+                <Highlight text={props.name.clone()} query={props.query.clone()} />
+            </span>
+        };
+    }
+
+    let on_blur = {
+        let is_editing = is_editing.clone();
+        Callback::from(move |_| is_editing.set(false))
+    };
+
+    html! {
+        <input
+            class={classes!("border-none", "focus:ring-0", "focus:border-blue-300", "rounded-lg", props.class.clone())}
+            type="text"
+            value={props.name.clone()}
+            onkeydown={on_keydown}
+            onblur={on_blur}
+            autofocus={*is_editing}
+        />
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_every_match_ignoring_case() {
+        assert_eq!(find_all("Ship the shipment", "SHIP"), [(0, 4), (9, 13)]);
+        assert_eq!(find_all("aaa", "aa"), [(0, 2)]);
+        assert_eq!(find_all("anything", ""), []);
+        assert_eq!(find_all("Ship", "dock"), []);
+    }
+
+    #[test]
+    fn match_offsets_survive_lowercasing() {
+        // "İ" takes two bytes but lowercases to three.
+        let text = "İstanbul";
+        assert_eq!(find_all(text, "STAN"), [(2, 6)]);
+        assert_eq!(&text[2..6], "stan");
+        // The Kelvin sign takes three bytes but lowercases to one.
+        let text = "\u{212A}elvin, kelvin";
+        assert_eq!(find_all(text, "kelvin"), [(0, 8), (10, 16)]);
+        assert_eq!(find_all("Straße", "STRASSE"), []);
+        assert_eq!(find_all("STRAßE", "straße"), [(0, 7)]);
+    }
+
+    #[test]
+    fn snippets_keep_the_text_around_the_match() {
+        assert_eq!(snippet("Call\n  the   dock", "dock"), "Call the dock");
+        assert_eq!(snippet("Call the dock", "ship"), "Call the dock");
+        let text = format!("{}NEEDLE{}", "é".repeat(50), "ü".repeat(100));
+        assert_eq!(
+            snippet(&text, "needle"),
+            format!("…{}NEEDLE{}…", "é".repeat(30), "ü".repeat(60))
+        );
+        let text = format!("{}needle", "\u{212A}".repeat(10));
+        assert_eq!(snippet(&text, "NEEDLE"), text);
+    }
+}
@@ -0,0 +1,459 @@
+use crate::cell::{CellKind, CellValue, Timeline};
+use crate::column::{ColumnDef, ColumnScope};
+use crate::date::today;
+use crate::detail::{use_open_item, ItemRef};
+use crate::filter::BoardFilter;
+use crate::sort::sort_tasks;
+use crate::store::{use_board, Board, BoardAction, Cells};
+use chrono::{Datelike, Duration, NaiveDate};
+use gloo_events::EventListener;
+use wasm_bindgen::JsCast;
+use web_sys::MouseEvent;
+use yew::prelude::*;
+
+/// How wide one day is on the chart, in pixels.
+const DAY_WIDTH: i32 = 28;
+
+/// Days of room left before the first bar and after the last.
+const MARGIN_DAYS: i64 = 3;
+
+/// How far from today a span may reach and still be drawn. A mistyped year
+/// would otherwise stretch the chart over centuries of empty days.
+const REACH_DAYS: i64 = 2 * 366;
+
+/// What dragging a bar changes.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Handle {
+    Move,
+    Start,
+    End,
+}
+
+/// A bar being dragged: which item, which part of the bar, where the drag
+/// began and the span before it.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+struct Drag {
+    item: ItemRef,
+    handle: Handle,
+    x: i32,
+    timeline: Timeline,
+}
+
+impl Drag {
+    /// The span after moving the grabbed part by `days`. Resizing stops at a
+    /// single day rather than turning the bar around.
+    fn shifted(self, days: i64) -> Timeline {
+        let Timeline { start, end } = self.timeline;
+        let days = Duration::days(days);
+        match self.handle {
+            Handle::Move => Timeline {
+                start: start + days,
+                end: end + days,
+            },
+            Handle::Start => Timeline {
+                start: (start + days).min(end),
+                end,
+            },
+            Handle::End => Timeline {
+                start,
+                end: (end + days).max(start),
+            },
+        }
+    }
+
+    /// Whole days the pointer has moved since the drag began.
+    fn days(self, x: i32) -> i64 {
+        ((x - self.x) as f64 / DAY_WIDTH as f64).round() as i64
+    }
+}
+
+/// The timeline column items of `scope` are drawn by: the first one.
+fn timeline_column(board: &Board, scope: ColumnScope) -> Option<&ColumnDef> {
+    board
+        .columns_for(scope)
+        .iter()
+        .find(|column| column.kind == CellKind::Timeline)
+}
+
+fn timeline(board: &Board, scope: ColumnScope, cells: &Cells) -> Option<Timeline> {
+    match timeline_column(board, scope)?.cell(cells) {
+        CellValue::Timeline(timeline) => timeline,
+        _ => None,
+    }
+}
+
+/// Whether `timeline` is close enough to today to be drawn.
+fn in_reach(timeline: Timeline, today: NaiveDate) -> bool {
+    timeline.start >= today - Duration::days(REACH_DAYS)
+        && timeline.end <= today + Duration::days(REACH_DAYS)
+}
+
+/// Every span the filter shows.
+fn spans(board: &Board, filter: &BoardFilter) -> Vec<Timeline> {
+    let mut spans = Vec::new();
+    for group in &board.groups {
+        for task in filter.visible_tasks(board, &group.tasks) {
+            spans.extend(timeline(board, ColumnScope::Task, &task.cells));
+            for subitem in filter.visible_subitems(board, task) {
+                spans.extend(timeline(board, ColumnScope::Subitem, &subitem.cells));
+            }
+        }
+    }
+    spans
+}
+
+/// The days the chart covers: every span in reach, with some room either
+/// side, or the next few weeks when nothing is planned yet.
+fn days(spans: &[Timeline], today: NaiveDate) -> Vec<NaiveDate> {
+    let spans: Vec<Timeline> = spans
+        .iter()
+        .copied()
+        .filter(|span| in_reach(*span, today))
+        .collect();
+    let first = spans
+        .iter()
+        .map(|span| span.start)
+        .min()
+        .unwrap_or(today)
+        .min(today);
+    let last = spans
+        .iter()
+        .map(|span| span.end)
+        .max()
+        .unwrap_or(today + Duration::days(28))
+        .max(today);
+    let first = first - Duration::days(MARGIN_DAYS);
+    let last = last + Duration::days(MARGIN_DAYS);
+    first.iter_days().take_while(|day| *day <= last).collect()
+}
+
+#[derive(Properties, PartialEq)]
+pub struct GanttProps {
+    #[prop_or_default]
+    pub filter: BoardFilter,
+}
+
+/// The board's tasks as bars on a timeline by their timeline column,
+/// grouped like the board, with each task's subitems under it. Drag a bar to
+/// move it or its ends to resize it, and click a name to open the item.
+#[function_component(Gantt)]
+pub fn gantt(props: &GanttProps) -> Html {
+    let board = use_board();
+    let open_item = use_open_item();
+    let drag = use_state(|| None::<Drag>);
+    // Whole days the dragged bar has moved so far, for the preview.
+    let moved = use_state(|| 0i64);
+
+    {
+        let dispatcher = board.dispatcher();
+        let drag_handle = drag.clone();
+        let moved = moved.clone();
+        let columns = (
+            timeline_column(&board, ColumnScope::Task).map(|column| column.id),
+            timeline_column(&board, ColumnScope::Subitem).map(|column| column.id),
+        );
+        use_effect_with(*drag, move |drag| {
+            let listeners = drag.map(|drag| {
+                let document = web_sys::window()
+                    .and_then(|window| window.document())
+                    .expect("document should be available");
+                let on_move = {
+                    let moved = moved.clone();
+                    EventListener::new(&document, "mousemove", move |e| {
+                        if let Some(e) = e.dyn_ref::<MouseEvent>() {
+                            moved.set(drag.days(e.client_x()));
+                        }
+                    })
+                };
+                let on_up = EventListener::new(&document, "mouseup", move |e| {
+                    let Some(e) = e.dyn_ref::<MouseEvent>() else {
+                        return;
+                    };
+                    let timeline = drag.shifted(drag.days(e.client_x()));
+                    let value = CellValue::Timeline(Some(timeline));
+                    let item = drag.item;
+                    match (item.scope, columns) {
+                        // A click without a move leaves the span alone.
+                        _ if timeline == drag.timeline => {}
+                        (ColumnScope::Task, (Some(column), _)) => {
+                            dispatcher.dispatch(BoardAction::UpdateTask {
+                                task: item.id,
+                                column,
+                                value,
+                            })
+                        }
+                        (ColumnScope::Subitem, (_, Some(column))) => {
+                            dispatcher.dispatch(BoardAction::UpdateSubitem {
+                                subitem: item.id,
+                                column,
+                                value,
+                            })
+                        }
+                        _ => {}
+                    }
+                    moved.set(0);
+                    drag_handle.set(None);
+                });
+                (on_move, on_up)
+            });
+            move || drop(listeners)
+        });
+    }
+
+    if timeline_column(&board, ColumnScope::Task).is_none()
+        && timeline_column(&board, ColumnScope::Subitem).is_none()
+    {
+        return html! {
+            <p class="text-sm text-gray-500">{"Add a timeline column to plan the board on a Gantt chart."}</p>
+        };
+    }
+
+    let today = today();
+    let spans = spans(&board, &props.filter);
+    let out_of_reach = spans.iter().filter(|span| !in_reach(**span, today)).count();
+    let days = days(&spans, today);
+    let first = days[0];
+    let width = days.len() as i32 * DAY_WIDTH;
+    let offset = |day: NaiveDate| (day - first).num_days() as i32 * DAY_WIDTH;
+
+    let name = |item: ItemRef, name: &str, subitem: bool| {
+        let on_open = {
+            let open_item = open_item.clone();
+            Callback::from(move |_| open_item.set(Some(item)))
+        };
+        html! {
+            <button
+                class={classes!("block", "w-full", "text-sm", "text-left", "truncate", "hover:text-blue-600", subitem.then_some("pl-6 text-gray-600"))}
+                title={name.to_string()}
+                onclick={on_open}
+            >
+                { if subitem { format!("↳ {}", name) } else { name.to_string() } }
+            </button>
+        }
+    };
+
+    let bar = |item: ItemRef, timeline: Option<Timeline>, color: &str, subitem: bool| {
+        let Some(timeline) = timeline.filter(|timeline| in_reach(*timeline, today)) else {
+            return html! {};
+        };
+        let grab = |handle: Handle| {
+            let drag = drag.clone();
+            let moved = moved.clone();
+            Callback::from(move |e: MouseEvent| {
+                if e.button() != 0 {
+                    return;
+                }
+                // Keep the handles from also starting a move, and the page
+                // from selecting text.
+                e.stop_propagation();
+                e.prevent_default();
+                moved.set(0);
+                drag.set(Some(Drag {
+                    item,
+                    handle,
+                    x: e.client_x(),
+                    timeline,
+                }));
+            })
+        };
+        let shown = match *drag {
+            Some(drag) if drag.item == item => drag.shifted(*moved),
+            _ => timeline,
+        };
+        let style = format!(
+            "left: {}px; width: {}px;",
+            offset(shown.start),
+            shown.days() as i32 * DAY_WIDTH,
+        );
+        let title = format!(
+            "{} – {} ({})",
+            shown.start.format("%b %-d"),
+            shown.end.format("%b %-d"),
+            match shown.days() {
+                1 => "1 day".to_string(),
+                days => format!("{} days", days),
+            },
+        );
+        let colors = if subitem {
+            format!("bg-{}-200 border border-{}-500", color, color)
+        } else {
+            format!("bg-{}-500", color)
+        };
+        html! {
+            <div
+                class={format!("absolute top-1 bottom-1 rounded cursor-grab select-none {}", colors)}
+                style={style}
+                title={title}
+                onmousedown={grab(Handle::Move)}
+            >
+                <div class="absolute inset-y-0 left-0 w-2 cursor-ew-resize" onmousedown={grab(Handle::Start)} />
+                <div class="absolute inset-y-0 right-0 w-2 cursor-ew-resize" onmousedown={grab(Handle::End)} />
+            </div>
+        }
+    };
+
+    let row = |label: Html, bar: Html| {
+        html! {
+            <div class="flex border-b">
+                <div class="sticky left-0 z-10 flex-shrink-0 w-64 px-2 py-1 bg-white border-r">{ label }</div>
+                <div class="relative flex-shrink-0 h-8" style={format!("width: {}px;", width)}>{ bar }</div>
+            </div>
+        }
+    };
+
+    html! {
+        <>
+            { if out_of_reach > 0 {
+                html! {
+                    <p class="mb-2 text-sm text-amber-700">
+                        { match out_of_reach {
+                            1 => "1 span is more than two years from today and isn't shown. Check its dates.".to_string(),
+                            count => format!("{} spans are more than two years from today and aren't shown. Check their dates.", count),
+                        } }
+                    </p>
+                }
+            } else {
+                html! {}
+            }}
+            <div class="relative overflow-x-auto border rounded">
+                <div class="flex border-b bg-gray-50">
+                    <div class="sticky left-0 z-10 flex-shrink-0 w-64 px-2 py-1 text-sm font-semibold bg-gray-50 border-r">{"Task"}</div>
+                    <div class="flex flex-shrink-0">
+                        { for days.iter().map(|&day| html! {
+                            <div
+                                class={classes!(
+                                    "flex-shrink-0", "text-xs", "text-center", "border-l",
+                                    if day == today { "bg-blue-500 text-white" } else { "text-gray-500" },
+                                )}
+                                style={format!("width: {}px;", DAY_WIDTH)}
+                                title={day.format("%a %b %-d, %Y").to_string()}
+                            >
+                                <div class="h-4 font-semibold">
+                                    { if day.day() == 1 || day == first { day.format("%b").to_string() } else { String::new() } }
+                                </div>
+                                <div>{ day.day() }</div>
+                            </div>
+                        }) }
+                    </div>
+                </div>
+                <div class="relative">
+                    <div
+                        class="absolute inset-y-0 w-px bg-blue-400 pointer-events-none"
+                        style={format!("left: calc(16rem + {}px);", offset(today) + DAY_WIDTH / 2)}
+                    />
+                    { for board.groups.iter().map(|group| {
+                        let mut tasks = props.filter.visible_tasks(&board, &group.tasks);
+                        if props.filter.is_searching() && tasks.is_empty() {
+                            return html! {};
+                        }
+                        sort_tasks(&board, &mut tasks, &board.default_sort);
+                        html! {
+                            <section key={group.id}>
+                                <div class={format!("px-2 py-1 text-sm font-semibold border-b text-{}-600", group.color)}>
+                                    <span class="sticky left-2">{ &group.name }</span>
+                                </div>
+                                { for tasks.into_iter().map(|task| {
+                                    let item = ItemRef {
+                                        scope: ColumnScope::Task,
+                                        id: task.id,
+                                    };
+                                    html! {
+                                        <div key={task.id}>
+                                            { row(
+                                                name(item, &task.name, false),
+                                                bar(item, timeline(&board, ColumnScope::Task, &task.cells), &group.color, false),
+                                            ) }
+                                            { for props.filter.visible_subitems(&board, task).into_iter().map(|subitem| {
+                                                let item = ItemRef {
+                                                    scope: ColumnScope::Subitem,
+                                                    id: subitem.id,
+                                                };
+                                                row(
+                                                    name(item, &subitem.name, true),
+                                                    bar(item, timeline(&board, ColumnScope::Subitem, &subitem.cells), &group.color, true),
+                                                )
+                                            }) }
+                                        </div>
+                                    }
+                                }) }
+                            </section>
+                        }
+                    }) }
+                </div>
+            </div>
+        </>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 3, day).unwrap()
+    }
+
+    fn drag(handle: Handle) -> Drag {
+        Drag {
+            item: ItemRef {
+                scope: ColumnScope::Task,
+                id: 1,
+            },
+            handle,
+            x: 100,
+            timeline: Timeline::new(day(10), day(14)),
+        }
+    }
+
+    #[test]
+    fn moving_keeps_the_length() {
+        assert_eq!(
+            drag(Handle::Move).shifted(3),
+            Timeline::new(day(13), day(17))
+        );
+        assert_eq!(
+            drag(Handle::Move).shifted(-9),
+            Timeline::new(day(1), day(5))
+        );
+        assert_eq!(drag(Handle::Move).shifted(0), drag(Handle::Move).timeline);
+    }
+
+    #[test]
+    fn resizing_stops_at_one_day() {
+        assert_eq!(
+            drag(Handle::Start).shifted(-2),
+            Timeline::new(day(8), day(14))
+        );
+        assert_eq!(
+            drag(Handle::Start).shifted(10),
+            Timeline::new(day(14), day(14))
+        );
+        assert_eq!(
+            drag(Handle::End).shifted(2),
+            Timeline::new(day(10), day(16))
+        );
+        assert_eq!(
+            drag(Handle::End).shifted(-10),
+            Timeline::new(day(10), day(10))
+        );
+    }
+
+    #[test]
+    fn days_round_to_the_nearest() {
+        let drag = drag(Handle::Move);
+        assert_eq!(drag.days(100), 0);
+        assert_eq!(drag.days(100 + DAY_WIDTH / 2 - 1), 0);
+        assert_eq!(drag.days(100 + DAY_WIDTH * 2), 2);
+        assert_eq!(drag.days(100 - DAY_WIDTH * 3 + 2), -3);
+    }
+
+    #[test]
+    fn mistyped_years_stay_off_the_chart() {
+        let today = day(1);
+        let typo = Timeline::new(NaiveDate::from_ymd_opt(204, 3, 1).unwrap(), day(5));
+        assert!(!in_reach(typo, today));
+        let days = days(&[Timeline::new(day(10), day(14)), typo], today);
+        assert_eq!(days.first(), Some(&(today - Duration::days(MARGIN_DAYS))));
+        assert_eq!(days.last(), Some(&(day(14) + Duration::days(MARGIN_DAYS))));
+    }
+}
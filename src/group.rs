@@ -1,57 +1,118 @@
+use crate::capture::CaptureDialog;
+use crate::cell::{CellKind, CellValue, FileRef};
+use crate::column::{grid_template, ColumnScope};
+use crate::date::{due, today};
+use crate::detail::{use_open_item, ItemRef};
+use crate::editor::CellEditor;
+use crate::filter::{BoardFilter, Matches, NameInput};
+use crate::menu::{MenuItem, RowMenu};
+use crate::outreach::rollup;
+use crate::regroup::Bucket;
+use crate::sort::{sort_tasks, SortColumn, SortHeader, SortKey};
+use crate::status::StatusBar;
+use crate::store::{use_board, BoardAction, Id, TaskData};
 use crate::subitem::Subitem;
+use crate::summary::GroupFooter;
 use web_sys::HtmlInputElement;
 use web_sys::KeyboardEvent;
 use yew::prelude::*;
-use yew::use_effect_with;
-
-#[derive(Properties, PartialEq)]
-pub struct GroupProps {
-    pub name: String,
-    pub color: String,
-    pub on_update_name: Callback<(usize, String)>,
-    pub index: usize,
-}
 
+/// What a [`Group`] shows.
 #[derive(Clone, PartialEq)]
-pub struct TaskData {
-    name: String,
-    subitems: Vec<SubitemData>,
+pub enum GroupSource {
+    /// A group on the board.
+    Group(Id),
+    /// Tasks gathered from every group by "Group by". Edits go to the tasks
+    /// themselves; the bucket can't be renamed, deleted or added to.
+    Bucket(Bucket),
 }
 
-#[derive(Clone, PartialEq)]
-pub struct SubitemData {
-    name: String,
+#[derive(Properties, PartialEq)]
+pub struct GroupProps {
+    pub source: GroupSource,
+    #[prop_or_default]
+    pub filter: BoardFilter,
 }
 
 #[function_component(Group)]
 pub fn group(props: &GroupProps) -> Html {
-    let tasks = use_state(|| Vec::<TaskData>::new());
-    let group_name = use_state(|| props.name.clone());
+    let board = use_board();
     let is_expanded = use_state(|| false);
     let is_editing = use_state(|| false);
-
-    let random_color = &props.color;
+    let sort = use_state(|| board.default_sort.clone());
 
     {
-        let group_name = group_name.clone();
-        use_effect_with(props.name.clone(), move |name| {
-            group_name.set(name.clone());
-            || ()
+        let sort = sort.clone();
+        use_effect_with(board.default_sort.clone(), move |default_sort| {
+            sort.set(default_sort.clone());
         });
     }
 
-    let task_count = tasks.len();
-    let subitem_count: usize = tasks.iter().map(|task| task.subitems.len()).sum();
+    let group = match &props.source {
+        GroupSource::Group(id) => board.group(*id),
+        GroupSource::Bucket(_) => None,
+    };
+    let group_id = group.map(|group| group.id);
+    let all_tasks: Vec<&TaskData> = match &props.source {
+        GroupSource::Group(_) => group
+            .map(|group| group.tasks.iter().collect())
+            .unwrap_or_default(),
+        GroupSource::Bucket(bucket) => bucket
+            .tasks
+            .iter()
+            .filter_map(|id| board.task(*id))
+            .collect(),
+    };
+    let mut tasks = props
+        .filter
+        .visible_tasks(&board, all_tasks.iter().copied());
+    sort_tasks(&board, &mut tasks, &sort);
+    let has_matches = props.filter.is_searching() && !tasks.is_empty();
+
+    {
+        let is_expanded = is_expanded.clone();
+        use_effect_with(
+            (props.filter.text.clone(), has_matches),
+            move |(_, has_matches)| {
+                if *has_matches {
+                    is_expanded.set(true);
+                }
+            },
+        );
+    }
+
+    let (name, random_color) = match (&props.source, group) {
+        (GroupSource::Group(_), Some(group)) => (&group.name, &group.color),
+        (GroupSource::Bucket(bucket), _) => (&bucket.name, &bucket.color),
+        (GroupSource::Group(_), None) => return html! {},
+    };
+
+    let task_count = all_tasks.len();
+    let subitem_count: usize = all_tasks.iter().map(|task| task.subitems.len()).sum();
+
+    let statuses: Option<Vec<Option<Id>>> = board
+        .columns
+        .iter()
+        .find(|column| column.kind == CellKind::Status)
+        .map(|column| {
+            all_tasks
+                .iter()
+                .map(|task| match column.cell(&task.cells) {
+                    CellValue::Status(status) => status,
+                    _ => None,
+                })
+                .collect()
+        });
 
     let on_add_task = {
-        let tasks = tasks.clone();
+        let board = board.clone();
         Callback::from(move |new_task_name: String| {
-            let mut new_tasks = (*tasks).clone();
-            new_tasks.push(TaskData {
-                name: new_task_name,
-                subitems: Vec::new(),
-            });
-            tasks.set(new_tasks);
+            if let Some(group) = group_id {
+                board.dispatch(BoardAction::AddTask {
+                    group,
+                    name: new_task_name,
+                });
+            }
         })
     };
 
@@ -63,22 +124,31 @@ pub fn group(props: &GroupProps) -> Html {
     };
 
     let on_group_name_change = {
-        let group_name = group_name.clone();
+        let board = board.clone();
         let is_editing = is_editing.clone();
-        let on_update_name = props.on_update_name.clone();
-        let index = props.index;
         Callback::from(move |e: KeyboardEvent| {
             if e.key() == "Enter" {
-                if let Some(input) = e.target_dyn_into::<HtmlInputElement>() {
-                    let new_name = input.value();
-                    group_name.set(new_name.clone());
-                    on_update_name.emit((index, new_name));
+                let input = e.target_dyn_into::<HtmlInputElement>();
+                if let (Some(group), Some(input)) = (group_id, input) {
+                    board.dispatch(BoardAction::RenameGroup {
+                        group,
+                        name: input.value(),
+                    });
                     is_editing.set(false);
                 }
             }
         })
     };
 
+    let on_delete = {
+        let board = board.clone();
+        Callback::from(move |_| {
+            if let Some(group) = group_id {
+                board.dispatch(BoardAction::DeleteGroup { group });
+            }
+        })
+    };
+
     let on_edit_start = {
         let is_editing = is_editing.clone();
         Callback::from(move |_| {
@@ -93,6 +163,31 @@ pub fn group(props: &GroupProps) -> Html {
         })
     };
 
+    let on_sort = {
+        let sort = sort.clone();
+        Callback::from(move |keys: Vec<SortKey>| sort.set(keys))
+    };
+
+    let on_reset_sort = {
+        let sort = sort.clone();
+        let default_sort = board.default_sort.clone();
+        Callback::from(move |_| sort.set(default_sort.clone()))
+    };
+
+    let on_save_sort = {
+        let board = board.clone();
+        let sort = sort.clone();
+        Callback::from(move |_| {
+            board.dispatch(BoardAction::SetDefaultSort {
+                sort: (*sort).clone(),
+            });
+        })
+    };
+
+    if props.filter.is_searching() && !has_matches {
+        return html! {};
+    }
+
     html! {
         <div class={format!("p-4 min-h-24 border rounded-lg my-4 border-l-8 border-{}-500", random_color)}>
             <div class="flex items-center justify-between">
@@ -105,13 +200,22 @@ pub fn group(props: &GroupProps) -> Html {
                             <input
                                 class={format!("text-xl font-semibold border focus:outline-none font-poppins text-{}-500", random_color)}
                                 type="text"
-                                value={(*group_name).clone()}
+                                value={name.clone()}
                                 onkeydown={on_group_name_change}
                                 onblur={on_edit_end.clone()}
                                 autofocus=true
                                 style="font-family: 'Poppins', 'Roboto', 'Noto Sans Hebrew', 'Noto Kufi Arabic', sans-serif;"
                             />
                         }
+                    } else if group.is_none() {
+                        html! {
+                            <span
+                                class={format!("text-xl font-semibold font-poppins text-{}-500", random_color)}
+                                style="font-family: 'Poppins', 'Roboto', 'Noto Sans Hebrew', 'Noto Kufi Arabic', sans-serif; padding: 4px;"
+                            >
+                                { name }
+                            </span>
+                        }
                     } else {
                         html! {
                             <span
@@ -119,60 +223,74 @@ pub fn group(props: &GroupProps) -> Html {
                                 onclick={on_edit_start}
                                 style="font-family: 'Poppins', 'Roboto', 'Noto Sans Hebrew', 'Noto Kufi Arabic', sans-serif; cursor: pointer; padding: 4px;"
                             >
-                                { &*group_name }
+                                { name }
                             </span>
                         }
                     }}
                 </div>
-                <span class="text-sm text-gray-500">
-                    { format!("{} Tasks / {} Subitems", task_count, subitem_count) }
-                </span>
+                <div class="flex items-center">
+                    { if let Some(values) = statuses {
+                        html! { <StatusBar values={values} /> }
+                    } else {
+                        html! {}
+                    }}
+                    { if props.filter.rules.is_active(&board) && tasks.is_empty() {
+                        html! { <span class="px-2 mr-2 text-xs text-gray-500 bg-gray-100 rounded">{"Empty under filter"}</span> }
+                    } else {
+                        html! {}
+                    }}
+                    <span class="text-sm text-gray-500">
+                        { format!("{} Tasks / {} Subitems", task_count, subitem_count) }
+                    </span>
+                    { if group.is_some() {
+                        html! { <RowMenu items={vec![MenuItem::new("Delete", on_delete)]} /> }
+                    } else {
+                        html! {}
+                    }}
+                </div>
             </div>
             { if *is_expanded {
                 html! {
                     <>
-                    <div class="grid grid-cols-7 gap-4 mt-2 ml-8 text-left font-semibold text-sm text-gray-1000">
-                        <span>{"Task"}</span>
-                        <span>{"Date"}</span>
-                        <span>{"Area"}</span>
-                        <span>{"Project Owner"}</span>
-                        <span>{"Notes"}</span>
-                        <span>{"Files / Image Capture"}</span>
-                        <span>{"Budget/Price"}</span>
+                    <div class="grid gap-4 mt-2 ml-8 text-left font-semibold text-sm text-gray-1000" style={grid_template(&board.columns)}>
+                        <SortHeader label="Task" column={SortColumn::Name} keys={(*sort).clone()} on_sort={on_sort.clone()} />
+                        { for board.columns.iter().map(|column| html! {
+                            <SortHeader
+                                key={column.id}
+                                label={column.name.clone()}
+                                column={SortColumn::Column(column.id)}
+                                keys={(*sort).clone()}
+                                on_sort={on_sort.clone()}
+                            />
+                        }) }
                     </div>
+                    { if *sort != board.default_sort {
+                        html! {
+                            <div class="flex items-center mt-1 ml-8 space-x-3 text-xs">
+                                <button class="text-blue-600" onclick={on_save_sort}>{"Save as board default"}</button>
+                                <button class="text-gray-500" onclick={on_reset_sort}>{"Reset"}</button>
+                            </div>
+                        }
+                    } else {
+                        html! {}
+                    }}
                     <ul class="mt-4">
-                        { for (*tasks).iter().enumerate().map(|(index, task)| {
-                            let cloned_tasks = tasks.clone();
-                            html! {
-                                <>
-                                <Task
-                                    name={task.name.clone()}
-                                    color={props.color.clone()}
-                                    subitems={task.subitems.clone()}
-                                    on_add_subitem={{
-                                        let cloned_tasks = cloned_tasks.clone();
-                                        Callback::from(move |subitem_name: String| {
-                                            let mut new_tasks = (*cloned_tasks).clone();
-                                            if let Some(task) = new_tasks.get_mut(index) {
-                                                task.subitems.push(SubitemData { name: subitem_name.clone() });
-                                            }
-                                            cloned_tasks.set(new_tasks);
-                                        })
-                                    }}
-                                    on_update_name={Callback::from(move |new_task_name: String| {
-                                        let mut new_tasks = (*cloned_tasks).clone();
-                                        if let Some(task) = new_tasks.get_mut(index) {
-                                            task.name = new_task_name.clone();
-                                        }
-                                        cloned_tasks.set(new_tasks);
-                                    })}
-                                />
-                                </>
-                            }
+                        { for tasks.iter().map(|task| html! {
+                            <Task key={task.id} id={task.id} color={random_color.clone()} filter={props.filter.clone()} />
                         })}
+                        { if tasks.is_empty() && !all_tasks.is_empty() {
+                            html! { <li class="ml-4 text-sm text-gray-500">{"No tasks match the filter."}</li> }
+                        } else {
+                            html! {}
+                        }}
 
-                        <AddTaskRow on_add={on_add_task.clone()} />
+                        { if group.is_some() {
+                            html! { <AddTaskRow on_add={on_add_task.clone()} /> }
+                        } else {
+                            html! {}
+                        }}
                     </ul>
+                    <GroupFooter tasks={tasks.iter().map(|task| task.id).collect::<Vec<_>>()} />
                     </>
                 }
             } else {
@@ -184,35 +302,40 @@ pub fn group(props: &GroupProps) -> Html {
 
 #[derive(Properties, PartialEq)]
 pub struct TaskProps {
-    pub name: String,
+    pub id: Id,
     pub color: String,
-    pub subitems: Vec<SubitemData>,
-    pub on_add_subitem: Callback<String>,
-    pub on_update_name: Callback<String>,
+    #[prop_or_default]
+    pub filter: BoardFilter,
 }
 
 #[function_component(Task)]
 pub fn task(props: &TaskProps) -> Html {
-    let subitems = use_state(|| props.subitems.clone());
-    let task_name = use_state(|| props.name.clone());
+    let board = use_board();
+    let open_item = use_open_item();
     let is_expanded = use_state(|| false);
-    let date = use_state(|| "2024-08-11".to_string());
-    let area = use_state(|| "Area 1".to_string());
-    let owner = use_state(|| "Owner 1".to_string());
-    let notes = use_state(|| "Sample Note".to_string());
-    let files = use_state(|| "0".to_string());
-    let budget = use_state(|| "$0".to_string());
+    let is_capturing = use_state(|| false);
 
-    let task_name_callback = props.on_update_name.clone();
+    let task_id = props.id;
+    let has_matches = board
+        .task(task_id)
+        .is_some_and(|task| props.filter.opens_task(&board, task));
 
     {
-        let task_name = task_name.clone();
-        use_effect_with(props.name.clone(), move |name| {
-            task_name.set(name.clone());
-            || ()
-        });
+        let is_expanded = is_expanded.clone();
+        use_effect_with(
+            (props.filter.clone(), has_matches),
+            move |(_, has_matches)| {
+                if *has_matches {
+                    is_expanded.set(true);
+                }
+            },
+        );
     }
 
+    let Some(task) = board.task(task_id) else {
+        return html! {};
+    };
+
     let toggle_expand = {
         let is_expanded = is_expanded.clone();
         Callback::from(move |_| {
@@ -220,91 +343,143 @@ pub fn task(props: &TaskProps) -> Html {
         })
     };
 
-    let on_input_change = |setter: UseStateHandle<String>, on_update: Callback<String>| {
-        Callback::from(move |e: KeyboardEvent| {
-            if e.key() == "Enter" {
-                if let Some(input) = e.target_dyn_into::<HtmlInputElement>() {
-                    let value = input.value();
-                    setter.set(value.clone());
-                    on_update.emit(value);
-                }
-            }
+    let on_rename = {
+        let board = board.clone();
+        Callback::from(move |name: String| {
+            board.dispatch(BoardAction::RenameTask {
+                task: task_id,
+                name,
+            });
+        })
+    };
+
+    let on_cell_commit = |column: Id| {
+        let board = board.clone();
+        Callback::from(move |value: CellValue| {
+            board.dispatch(BoardAction::UpdateTask {
+                task: task_id,
+                column,
+                value,
+            });
         })
     };
 
+    let on_cell_attach = |column: Id| {
+        let board = board.clone();
+        Callback::from(move |files: Vec<FileRef>| {
+            board.dispatch(BoardAction::AttachFiles {
+                scope: ColumnScope::Task,
+                item: task_id,
+                column: Some(column),
+                files,
+            });
+        })
+    };
+
+    let on_open = {
+        let open_item = open_item.clone();
+        Callback::from(move |_| {
+            open_item.set(Some(ItemRef {
+                scope: ColumnScope::Task,
+                id: task_id,
+            }));
+        })
+    };
+
+    let on_delete = {
+        let board = board.clone();
+        Callback::from(move |_| {
+            board.dispatch(BoardAction::DeleteTask { task: task_id });
+        })
+    };
+
+    let on_capture = {
+        let is_capturing = is_capturing.clone();
+        Callback::from(move |_| is_capturing.set(true))
+    };
+
+    let on_capture_close = {
+        let is_capturing = is_capturing.clone();
+        Callback::from(move |_| is_capturing.set(false))
+    };
+
     let on_add_subitem = {
-        let subitems = subitems.clone();
-        let on_add_subitem = props.on_add_subitem.clone();
+        let board = board.clone();
         Callback::from(move |subitem_name: String| {
-            let mut new_subitems = (*subitems).clone();
-            new_subitems.push(SubitemData {
-                name: subitem_name.clone(),
+            board.dispatch(BoardAction::AddSubitem {
+                task: task_id,
+                name: subitem_name,
             });
-            subitems.set(new_subitems);
-            on_add_subitem.emit(subitem_name);
         })
     };
 
     html! {
         <>
-            <li class={format!("grid grid-cols-7 gap-0 items-center bg-white border border-{}-500 rounded-lg p-3 mb-2 shadow-sm border-l-8", props.color.clone())}>
+            <li
+                class={format!(
+                    "grid gap-0 items-center border border-{}-500 rounded-lg p-3 mb-2 shadow-sm border-l-8 {}",
+                    props.color,
+                    due(&board.columns, &task.cells, today()).map_or("bg-white", |due| due.row_class()),
+                )}
+                style={grid_template(&board.columns)}
+            >
                 <div class="flex items-center space-x-2">
                     <button onclick={toggle_expand} class="focus:outline-none">
                         { if *is_expanded { "v" } else { ">" } }
                     </button>
-                    <input
-                        class="text-base font-medium border-none focus:ring-0 focus:border-blue-300 rounded-lg"
-                        type="text"
-                        value={(*task_name).clone()}
-                        onkeydown={on_input_change(task_name.clone(), task_name_callback)}
+                    <NameInput
+                        class="text-base font-medium"
+                        name={task.name.clone()}
+                        query={props.filter.text.clone()}
+                        on_rename={on_rename}
                     />
+                    { if let Some(progress) = rollup(&board.subitem_columns, &task.subitems) {
+                        html! {
+                            <span class="text-xs whitespace-nowrap text-gray-500" title="Outreach across subitems">
+                                { progress.to_string() }
+                            </span>
+                        }
+                    } else {
+                        html! {}
+                    }}
+                    <button class="px-1 text-gray-400 hover:text-blue-600" onclick={on_open.reform(|_| ())} title="Open details">{"⤢"}</button>
+                    <RowMenu items={vec![
+                        MenuItem::new("Capture image", on_capture),
+                        MenuItem::new("Delete", on_delete),
+                    ]} />
                 </div>
-                <input class="text-sm border-none focus:ring-0 focus:border-blue-300" type="text" value={(*date).clone()} onkeydown={on_input_change(date.clone(), Callback::noop())} />
-                <input class="text-sm border-none focus:ring-0 focus:border-blue-300" type="text" value={(*area).clone()} onkeydown={on_input_change(area.clone(), Callback::noop())} />
-                <input class="text-sm border-none focus:ring-0 focus:border-blue-300" type="text" value={(*owner).clone()} onkeydown={on_input_change(owner.clone(), Callback::noop())} />
-                <input class="text-sm border-none focus:ring-0 focus:border-blue-300" type="text" value={(*notes).clone()} onkeydown={on_input_change(notes.clone(), Callback::noop())} />
-                <input class="text-sm border-none focus:ring-0 focus:border-blue-300" type="text" value={(*files).clone()} onkeydown={on_input_change(files.clone(), Callback::noop())} />
-                <input class="text-sm border-none focus:ring-0 focus:border-blue-300" type="text" value={(*budget).clone()} onkeydown={on_input_change(budget.clone(), Callback::noop())} />
+                { for board.columns.iter().map(|column| html! {
+                    <CellEditor
+                        key={column.id}
+                        value={column.cell(&task.cells)}
+                        on_commit={on_cell_commit(column.id)}
+                        on_attach={on_cell_attach(column.id)}
+                        options={column.options.clone()}
+                        currency={column.currency}
+                        on_open={on_open.clone()}
+                    />
+                }) }
+                <Matches cells={props.filter.matching_cells(&board, &board.columns, &task.cells)} query={props.filter.text.clone()} />
             </li>
+            { if *is_capturing {
+                html! { <CaptureDialog scope={ColumnScope::Task} item={task_id} on_close={on_capture_close} /> }
+            } else {
+                html! {}
+            }}
             { if *is_expanded {
                 html! {
                     <>
-                        <div class="grid grid-cols-7 gap-4 mt-6 ml-12 text-left font-semibold text-sm text-gray-1000">
+                        <div class="grid gap-4 mt-6 ml-12 text-left font-semibold text-sm text-gray-1000" style={grid_template(&board.subitem_columns)}>
                             <span>{"Subitem"}</span>
-                            <span>{"Date"}</span>
-                            <span>{"Area"}</span>
-                            <span>{"People - Sent/Responded"}</span>
-                            <span>{"Notes"}</span>
-                            <span>{"Files / Image Capture"}</span>
-                            <span>{"Budget/Price"}</span>
+                            { for board.subitem_columns.iter().map(|column| html! {
+                                <span key={column.id}>{ &column.name }</span>
+                            }) }
                         </div>
                         <ul class="ml-8 mt-4">
-                            { for (*subitems).iter().enumerate().map(|(index, subitem)| {
-                                let cloned_subitems = subitems.clone();
-                                html! {
-                                    <Subitem
-                                        name={subitem.name.clone()}
-                                        color={props.color.clone()}
-                                        on_update_name={Callback::from(move |new_name: String| {
-                                            let mut new_subitems = (*cloned_subitems).clone();
-                                            if let Some(subitem) = new_subitems.get_mut(index) {
-                                                subitem.name = new_name.clone();
-                                            }
-                                            cloned_subitems.set(new_subitems);
-                                        })}
-                                        on_update_date={Callback::noop()}
-                                        on_update_area={Callback::noop()}
-                                        on_update_people={Callback::noop()}
-                                        on_update_notes={Callback::noop()}
-                                        on_update_files={Callback::noop()}
-                                        on_update_budget={Callback::noop()}
-                                    />
-                                }
+                            { for props.filter.visible_subitems(&board, task).into_iter().map(|subitem| html! {
+                                <Subitem key={subitem.id} id={subitem.id} color={props.color.clone()} filter={props.filter.clone()} />
                             })}
-
-                            <li class="grid grid-cols-7 gap-4 mt-2 col-span-7">
-                                <AddSubitemRow on_add={on_add_subitem.clone()} />
-                            </li>
+                            <AddSubitemRow on_add={on_add_subitem.clone()} />
                         </ul>
                     </>
                 }
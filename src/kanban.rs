@@ -0,0 +1,282 @@
+use crate::cell::{CellKind, CellValue};
+use crate::column::{ColumnDef, ColumnScope};
+use crate::date::{relative, today};
+use crate::detail::{use_open_item, ItemRef};
+use crate::drag;
+use crate::filter::BoardFilter;
+use crate::people::Avatar;
+use crate::sort::sort_tasks;
+use crate::status::cycle_color;
+use crate::store::{use_board, Board, BoardAction, GroupData, Id, TaskData};
+use web_sys::{DragEvent, HtmlSelectElement};
+use yew::prelude::*;
+
+/// One column of cards: the value a task gets when dropped here, and how
+/// the lane is titled.
+struct Lane {
+    value: CellValue,
+    name: String,
+    color: String,
+}
+
+/// A lane for every label or option of `column`, then one for tasks with
+/// nothing set.
+fn lanes(board: &Board, column: &ColumnDef) -> Vec<Lane> {
+    let mut lanes: Vec<Lane> = match column.kind {
+        CellKind::Status => board
+            .status_labels
+            .iter()
+            .map(|label| Lane {
+                value: CellValue::Status(Some(label.id)),
+                name: label.name.clone(),
+                color: label.color.clone(),
+            })
+            .collect(),
+        _ => column
+            .options
+            .iter()
+            .enumerate()
+            .map(|(index, option)| Lane {
+                value: CellValue::Select(Some(option.clone())),
+                name: option.clone(),
+                color: cycle_color(index),
+            })
+            .collect(),
+    };
+    lanes.push(Lane {
+        value: CellValue::empty(column.kind),
+        name: format!("No {}", column.name),
+        color: "gray".to_string(),
+    });
+    lanes
+}
+
+/// Whether the board can be shown as lanes of this kind of column.
+pub fn can_lane_by(kind: CellKind) -> bool {
+    matches!(kind, CellKind::Status | CellKind::Select)
+}
+
+#[derive(Properties, PartialEq)]
+pub struct CardProps {
+    pub task: Id,
+    pub group: Id,
+    pub on_drag: Callback<Option<Id>>,
+}
+
+/// A task as a card: its name and group, then a preview of its owner, date
+/// and budget from the first column of each kind, and how many subitems it
+/// has. Click the name to open it.
+#[function_component(Card)]
+pub fn card(props: &CardProps) -> Html {
+    let board = use_board();
+    let open_item = use_open_item();
+    let (Some(task), Some(group)) = (board.task(props.task), board.group(props.group)) else {
+        return html! {};
+    };
+    let first = |kind: CellKind| {
+        board
+            .columns
+            .iter()
+            .find(|column| column.kind == kind)
+            .map(|column| (column, column.cell(&task.cells)))
+    };
+
+    let id = props.task;
+    let on_open = Callback::from(move |_| {
+        open_item.set(Some(ItemRef {
+            scope: ColumnScope::Task,
+            id,
+        }));
+    });
+    let on_drag_start = {
+        let on_drag = props.on_drag.clone();
+        Callback::from(move |e: DragEvent| {
+            drag::start(&e, id);
+            on_drag.emit(Some(id));
+        })
+    };
+    let on_drag_end = props.on_drag.reform(|_: DragEvent| None);
+
+    let subitems = task.subitems.len();
+
+    html! {
+        <li
+            class={format!("p-3 space-y-2 bg-white border border-l-4 border-{}-500 rounded-lg shadow-sm cursor-grab", group.color)}
+            draggable="true"
+            ondragstart={on_drag_start}
+            ondragend={on_drag_end}
+        >
+            <button class="block font-medium text-left hover:text-blue-600" onclick={on_open}>{ &task.name }</button>
+            <div class={format!("text-xs text-{}-500", group.color)}>{ &group.name }</div>
+            <div class="flex flex-wrap items-center gap-2 text-xs text-gray-600">
+                { match first(CellKind::Person) {
+                    Some((_, CellValue::Person(people))) if !people.is_empty() => html! {
+                        <span class="flex -space-x-1">
+                            { for people.iter().map(|person| html! { <Avatar key={*person} person={*person} /> }) }
+                        </span>
+                    },
+                    _ => html! {},
+                }}
+                { match first(CellKind::Date) {
+                    Some((_, CellValue::Date(Some(date)))) => html! {
+                        <span title={date.format("%b %-d, %Y").to_string()}>{ format!("📅 {}", relative(date, today())) }</span>
+                    },
+                    _ => html! {},
+                }}
+                { match first(CellKind::Money) {
+                    Some((column, CellValue::Money(Some(money)))) => html! {
+                        <span>{ column.currency.format(money) }</span>
+                    },
+                    _ => html! {},
+                }}
+                { match subitems {
+                    0 => html! {},
+                    1 => html! { <span>{"1 subitem"}</span> },
+                    count => html! { <span>{ format!("{} subitems", count) }</span> },
+                }}
+            </div>
+        </li>
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct KanbanProps {
+    #[prop_or_default]
+    pub filter: BoardFilter,
+}
+
+/// The board's tasks as cards in lanes, one per label or option of a status
+/// or dropdown column. Dragging a card to another lane sets that value.
+#[function_component(Kanban)]
+pub fn kanban(props: &KanbanProps) -> Html {
+    let board = use_board();
+    let lane_by = use_state(|| None::<Id>);
+    let dragging = use_state(|| None::<Id>);
+    // Dragover fires many times a second, so only a new lane re-renders.
+    let hovered = use_state_eq(|| None::<usize>);
+
+    let choices: Vec<&ColumnDef> = board
+        .columns
+        .iter()
+        .filter(|column| can_lane_by(column.kind))
+        .collect();
+    let Some(column) = lane_by
+        .and_then(|id| choices.iter().find(|column| column.id == id))
+        .or(choices.first())
+        .copied()
+    else {
+        return html! {
+            <p class="text-sm text-gray-500">{"Add a status or dropdown column to see the board as cards."}</p>
+        };
+    };
+
+    let on_lane_by = {
+        let lane_by = lane_by.clone();
+        let choices: Vec<Id> = choices.iter().map(|column| column.id).collect();
+        Callback::from(move |e: Event| {
+            if let Some(select) = e.target_dyn_into::<HtmlSelectElement>() {
+                lane_by.set(choices.get(select.selected_index() as usize).copied());
+            }
+        })
+    };
+
+    let on_drag = {
+        let dragging = dragging.clone();
+        let hovered = hovered.clone();
+        Callback::from(move |task: Option<Id>| {
+            dragging.set(task);
+            if task.is_none() {
+                hovered.set(None);
+            }
+        })
+    };
+
+    let mut tasks: Vec<(&TaskData, &GroupData)> = Vec::new();
+    for group in &board.groups {
+        let mut visible = props.filter.visible_tasks(&board, &group.tasks);
+        sort_tasks(&board, &mut visible, &board.default_sort);
+        tasks.extend(visible.into_iter().map(|task| (task, group)));
+    }
+    let lanes = lanes(&board, column);
+    // Tasks whose value is no longer a lane, such as a removed option, go
+    // with the empty ones.
+    let lane_of = |task: &TaskData| {
+        let value = column.cell(&task.cells);
+        lanes
+            .iter()
+            .position(|lane| lane.value == value)
+            .unwrap_or(lanes.len() - 1)
+    };
+
+    html! {
+        <div>
+            <label class="text-sm">
+                {"Lanes by "}
+                <select class="p-1 border rounded" onchange={on_lane_by}>
+                    { for choices.iter().map(|choice| html! {
+                        <option selected={choice.id == column.id}>{ &choice.name }</option>
+                    }) }
+                </select>
+            </label>
+            <div class="flex items-start mt-4 space-x-4 overflow-x-auto pb-4">
+                { for lanes.iter().enumerate().map(|(index, lane)| {
+                    let cards: Vec<_> = tasks.iter().filter(|(task, _)| lane_of(task) == index).collect();
+                    let on_drag_over = {
+                        let hovered = hovered.clone();
+                        Callback::from(move |e: DragEvent| {
+                            // Without this the lane doesn't accept the drop.
+                            e.prevent_default();
+                            hovered.set(Some(index));
+                        })
+                    };
+                    let on_drag_leave = {
+                        let hovered = hovered.clone();
+                        Callback::from(move |e: DragEvent| {
+                            if drag::left(&e) {
+                                hovered.set(None);
+                            }
+                        })
+                    };
+                    let on_drop = {
+                        let board = board.clone();
+                        let dragging = dragging.clone();
+                        let hovered = hovered.clone();
+                        let column = column.id;
+                        let value = lane.value.clone();
+                        Callback::from(move |e: DragEvent| {
+                            e.prevent_default();
+                            if let Some(task) = *dragging {
+                                board.dispatch(BoardAction::UpdateTask {
+                                    task,
+                                    column,
+                                    value: value.clone(),
+                                });
+                            }
+                            dragging.set(None);
+                            hovered.set(None);
+                        })
+                    };
+                    html! {
+                        <section
+                            key={index}
+                            class={classes!("flex-shrink-0", "w-72", "p-2", "rounded-lg", if *hovered == Some(index) { "bg-blue-50" } else { "bg-gray-100" })}
+                            ondragover={on_drag_over}
+                            ondragleave={on_drag_leave}
+                            ondrop={on_drop}
+                        >
+                            <h2 class={format!("flex items-center justify-between px-2 py-1 mb-2 text-sm font-semibold text-white rounded bg-{}-500", lane.color)}>
+                                <span>{ &lane.name }</span>
+                                <span>{ cards.len() }</span>
+                            </h2>
+                            <ul class="space-y-2 min-h-16">
+                                { for cards.iter().map(|(task, group)| html! {
+                                    <Card key={task.id} task={task.id} group={group.id} on_drag={on_drag.clone()} />
+                                }) }
+                            </ul>
+                        </section>
+                    }
+                }) }
+            </div>
+        </div>
+    }
+}
@@ -1,54 +1,378 @@
+use gloo_events::EventListener;
+use wasm_bindgen::JsCast;
+use web_sys::{
+    HtmlElement, HtmlInputElement, HtmlSelectElement, HtmlTextAreaElement, KeyboardEvent,
+};
 use yew::prelude::*;
+mod attachments;
+mod calendar;
+mod capture;
+mod cell;
+mod column;
+mod date;
+mod detail;
+mod drag;
+mod editor;
+mod files;
+mod filter;
+mod gantt;
 mod group;
+mod kanban;
+mod markdown;
+mod mentions;
+mod menu;
+mod outreach;
+mod people;
+mod regroup;
+mod rules;
+mod sort;
+mod status;
+mod storage;
+mod store;
 mod subitem;
-use group::Group;
+mod summary;
+mod trash;
+mod updates;
+use calendar::Calendar;
+use column::{ColumnDef, ColumnScope, ColumnsPanel};
+use detail::{DetailPanel, OpenItem};
+use filter::BoardFilter;
+use gantt::Gantt;
+use group::{Group, GroupSource};
+use kanban::Kanban;
+use mentions::MentionsPanel;
+use people::PeoplePanel;
 use rand::Rng;
+use regroup::{buckets, can_group_by};
+use rules::{FilterBuilder, Rules};
+use status::StatusLabelsPanel;
+use store::{BoardAction, BoardHandle, BoardStore};
+use summary::BoardTotal;
+use trash::TrashPanel;
+
+/// How the board's tasks are laid out.
+#[derive(Clone, Copy, PartialEq)]
+enum View {
+    Table,
+    Kanban,
+    Calendar,
+    Gantt,
+}
 
 #[function_component(App)]
 fn app() -> Html {
-    let groups = use_state(|| vec![
-        ("This is Sample Group 1".to_string(), "purple".to_string()),
-        ("This is Sample Group 2".to_string(), "red".to_string())
-    ]);
+    let board = use_reducer(|| BoardStore::new(storage::load()));
+    let show_trash = use_state(|| false);
+    let show_columns = use_state(|| false);
+    let show_mentions = use_state(|| false);
+    let assignee = use_state(|| None::<store::Id>);
+    let search = use_state(String::new);
+    let rules = use_state(Rules::default);
+    let show_filter = use_state(|| false);
+    let group_by = use_state(|| None::<store::Id>);
+    let view = use_state(|| View::Table);
+    let open_item: OpenItem = use_state(|| None);
+
+    use_effect_with(board.board.clone(), |board| {
+        storage::save(board);
+    });
+
+    {
+        let board = board.board.clone();
+        use_effect_with((), move |_| {
+            wasm_bindgen_futures::spawn_local(async move {
+                attachments::collect_garbage(&board).await;
+            });
+        });
+    }
+
+    {
+        let dispatcher = board.dispatcher();
+        use_effect_with((), move |_| {
+            let document = web_sys::window()
+                .and_then(|window| window.document())
+                .expect("document should be available");
+            let listener = EventListener::new(&document, "keydown", move |e| {
+                let Some(e) = e.dyn_ref::<KeyboardEvent>() else {
+                    return;
+                };
+                if !(e.ctrl_key() || e.meta_key()) {
+                    return;
+                }
+                // Text fields keep their own undo.
+                let target = e.target();
+                let editing = target.as_ref().is_some_and(|target| {
+                    target.is_instance_of::<HtmlInputElement>()
+                        || target.is_instance_of::<HtmlTextAreaElement>()
+                        || target
+                            .dyn_ref::<HtmlElement>()
+                            .is_some_and(HtmlElement::is_content_editable)
+                });
+                if editing {
+                    return;
+                }
+                match e.key().to_lowercase().as_str() {
+                    "z" if e.shift_key() => dispatcher.dispatch(BoardAction::Redo),
+                    "z" => dispatcher.dispatch(BoardAction::Undo),
+                    "y" => dispatcher.dispatch(BoardAction::Redo),
+                    _ => return,
+                }
+                e.prevent_default();
+            });
+            move || drop(listener)
+        });
+    }
 
     let on_add_group = {
-        let groups = groups.clone();
+        let board = board.clone();
         Callback::from(move |_| {
-            let mut new_groups = (*groups).clone();
-            let colors = vec!["red", "blue", "green", "purple", "orange"];
+            let colors = ["red", "blue", "green", "purple", "orange"];
             let mut rng = rand::thread_rng();
             let color = colors[rng.gen_range(0..colors.len())].to_string();
-            new_groups.push((format!("This is a Sample Group {}", new_groups.len()), color));
-            groups.set(new_groups);
+            board.dispatch(BoardAction::AddGroup {
+                name: format!("This is a Sample Group {}", board.groups.len()),
+                color,
+            });
+        })
+    };
+
+    let on_undo = {
+        let board = board.clone();
+        Callback::from(move |_| board.dispatch(BoardAction::Undo))
+    };
+
+    let on_redo = {
+        let board = board.clone();
+        Callback::from(move |_| board.dispatch(BoardAction::Redo))
+    };
+
+    let toggle_columns = {
+        let show_columns = show_columns.clone();
+        Callback::from(move |_| {
+            show_columns.set(!*show_columns);
+        })
+    };
+
+    let on_assignee = {
+        let board = board.clone();
+        let assignee = assignee.clone();
+        Callback::from(move |e: Event| {
+            if let Some(select) = e.target_dyn_into::<HtmlSelectElement>() {
+                let index = select.selected_index() as usize;
+                assignee.set(
+                    index
+                        .checked_sub(1)
+                        .and_then(|index| board.people.get(index))
+                        .map(|person| person.id),
+                );
+            }
+        })
+    };
+
+    let toggle_mentions = {
+        let show_mentions = show_mentions.clone();
+        Callback::from(move |_| {
+            show_mentions.set(!*show_mentions);
+        })
+    };
+
+    let on_search = {
+        let search = search.clone();
+        Callback::from(move |e: InputEvent| {
+            if let Some(input) = e.target_dyn_into::<HtmlInputElement>() {
+                search.set(input.value());
+            }
+        })
+    };
+
+    let on_search_keydown = {
+        let search = search.clone();
+        Callback::from(move |e: KeyboardEvent| {
+            if e.key() == "Escape" {
+                search.set(String::new());
+            }
+        })
+    };
+
+    let toggle_filter = {
+        let show_filter = show_filter.clone();
+        Callback::from(move |_| {
+            show_filter.set(!*show_filter);
         })
     };
 
-    let on_update_group_name = {
-        let groups = groups.clone();
-        Callback::from(move |(index, new_name): (usize, String)| {
-            let mut new_groups = (*groups).clone();
-            if let Some(group) = new_groups.get_mut(index) {
-                group.0 = new_name;
+    let on_rules = {
+        let rules = rules.clone();
+        Callback::from(move |new_rules: Rules| rules.set(new_rules))
+    };
+
+    let groupable: Vec<&ColumnDef> = board
+        .columns
+        .iter()
+        .filter(|column| can_group_by(column.kind))
+        .collect();
+
+    let on_group_by = {
+        let group_by = group_by.clone();
+        let groupable: Vec<store::Id> = groupable.iter().map(|column| column.id).collect();
+        Callback::from(move |e: Event| {
+            if let Some(select) = e.target_dyn_into::<HtmlSelectElement>() {
+                let index = select.selected_index() as usize;
+                group_by.set(
+                    index
+                        .checked_sub(1)
+                        .and_then(|index| groupable.get(index))
+                        .copied(),
+                );
             }
-            groups.set(new_groups);
+        })
+    };
+
+    let grouped_by = group_by.and_then(|id| groupable.iter().find(|column| column.id == id));
+
+    let filter = BoardFilter {
+        assignee: *assignee,
+        text: (*search).clone(),
+        rules: (*rules).clone(),
+    };
+
+    let on_view = |to: View| {
+        let view = view.clone();
+        Callback::from(move |_| view.set(to))
+    };
+    let view_button = |to: View, label: &str| {
+        let class = if *view == to {
+            "px-3 py-2 bg-blue-500 text-white"
+        } else {
+            "px-3 py-2 bg-white"
+        };
+        html! { <button onclick={on_view(to)} class={class}>{ label }</button> }
+    };
+
+    let toggle_trash = {
+        let show_trash = show_trash.clone();
+        Callback::from(move |_| {
+            show_trash.set(!*show_trash);
         })
     };
 
     html! {
-        <div class="p-4">
-            <h1 class="text-2xl font-bold">{"Sample Monday.com Functionality"}</h1>
-            <button onclick={on_add_group} class="mt-4 p-2 bg-blue-500 text-white rounded">{"Add Group"}</button>
-            <div class="mt-4 pb-4">
-                { for groups.iter().enumerate().map(|(index, (name, color))| html! {
-                    <Group
-                        name={name.clone()}
-                        color={color.clone()}
-                        on_update_name={on_update_group_name.clone()}
-                        index={index}
-                    />
-                }) }
-            </div>
-        </div>
+        <ContextProvider<BoardHandle> context={board.clone()}>
+            <ContextProvider<OpenItem> context={open_item}>
+                <div class="p-4">
+                    <h1 class="text-2xl font-bold">{"Sample Monday.com Functionality"}</h1>
+                    <button onclick={on_add_group} class="mt-4 p-2 bg-blue-500 text-white rounded">{"Add Group"}</button>
+                    <button onclick={on_undo} disabled={!board.can_undo()} class="mt-4 ml-2 p-2 border rounded disabled:text-gray-400" title="Undo (Ctrl+Z)">{"Undo"}</button>
+                    <button onclick={on_redo} disabled={!board.can_redo()} class="mt-4 ml-2 p-2 border rounded disabled:text-gray-400" title="Redo (Ctrl+Shift+Z)">{"Redo"}</button>
+                    <button onclick={toggle_columns} class="mt-4 ml-2 p-2 border rounded">{"Columns"}</button>
+                    <button onclick={toggle_filter} class="mt-4 ml-2 p-2 border rounded">
+                        { match rules.count(&board) {
+                            0 => "Filter".to_string(),
+                            count => format!("Filter ({})", count),
+                        } }
+                    </button>
+                    <button onclick={toggle_mentions} class="mt-4 ml-2 p-2 border rounded">{"Mentions"}</button>
+                    <button onclick={toggle_trash} class="mt-4 ml-2 p-2 border rounded">
+                        { format!("Trash ({})", board.trash.len()) }
+                    </button>
+                    <select onchange={on_assignee} class="mt-4 ml-2 p-2 border rounded" title="Show tasks assigned to">
+                        <option selected={assignee.is_none()}>{"Everyone"}</option>
+                        { for board.people.iter().map(|person| html! {
+                            <option selected={*assignee == Some(person.id)}>{ &person.name }</option>
+                        }) }
+                    </select>
+                    <span class="inline-flex mt-4 ml-2 overflow-hidden align-middle border rounded divide-x">
+                        { view_button(View::Table, "Table") }
+                        { view_button(View::Kanban, "Kanban") }
+                        { view_button(View::Calendar, "Calendar") }
+                        { view_button(View::Gantt, "Gantt") }
+                    </span>
+                    { if *view == View::Table {
+                        html! {
+                            <select onchange={on_group_by} class="mt-4 ml-2 p-2 border rounded" title="Group tasks by">
+                                <option selected={grouped_by.is_none()}>{"Group by: Groups"}</option>
+                                { for groupable.iter().map(|column| html! {
+                                    <option selected={*group_by == Some(column.id)}>{ format!("Group by: {}", column.name) }</option>
+                                }) }
+                            </select>
+                        }
+                    } else {
+                        html! {}
+                    }}
+                    <BoardTotal />
+                    <div class="flex items-center mt-4 space-x-3">
+                        <input
+                            class="w-80 p-2 border rounded"
+                            type="search"
+                            placeholder="Search tasks and subitems"
+                            value={(*search).clone()}
+                            oninput={on_search}
+                            onkeydown={on_search_keydown}
+                        />
+                        { if filter.is_searching() {
+                            let (results, groups) = filter.results(&board.board);
+                            html! {
+                                <span class="text-sm text-gray-500">
+                                    { format!(
+                                        "{} {} in {} {}",
+                                        results,
+                                        if results == 1 { "result" } else { "results" },
+                                        groups,
+                                        if groups == 1 { "group" } else { "groups" },
+                                    ) }
+                                </span>
+                            }
+                        } else {
+                            html! {}
+                        }}
+                    </div>
+                    { if *show_columns {
+                        html! {
+                            <>
+                                <ColumnsPanel scope={ColumnScope::Task} />
+                                <ColumnsPanel scope={ColumnScope::Subitem} />
+                                <StatusLabelsPanel />
+                                <PeoplePanel />
+                            </>
+                        }
+                    } else {
+                        html! {}
+                    }}
+                    { if *show_filter {
+                        html! { <FilterBuilder rules={(*rules).clone()} on_change={on_rules} /> }
+                    } else {
+                        html! {}
+                    }}
+                    { if *show_mentions { html! { <MentionsPanel /> } } else { html! {} } }
+                    { if *show_trash { html! { <TrashPanel /> } } else { html! {} } }
+                    <div class="mt-4 pb-4">
+                        { if *view == View::Kanban {
+                            html! { <Kanban filter={filter.clone()} /> }
+                        } else if *view == View::Calendar {
+                            html! { <Calendar filter={filter.clone()} /> }
+                        } else if *view == View::Gantt {
+                            html! { <Gantt filter={filter.clone()} /> }
+                        } else if let Some(column) = grouped_by {
+                            html! {
+                                for buckets(&board.board, column).into_iter().map(|bucket| {
+                                    let key = bucket.key.clone();
+                                    html! {
+                                        <Group key={key} source={GroupSource::Bucket(bucket)} filter={filter.clone()} />
+                                    }
+                                })
+                            }
+                        } else {
+                            html! {
+                                for board.groups.iter().map(|group| html! {
+                                    <Group key={group.id} source={GroupSource::Group(group.id)} filter={filter.clone()} />
+                                })
+                            }
+                        }}
+                    </div>
+                </div>
+                <DetailPanel />
+            </ContextProvider<OpenItem>>
+        </ContextProvider<BoardHandle>>
     }
 }
 
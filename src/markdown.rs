@@ -0,0 +1,222 @@
+use crate::people::{Avatar, Person};
+use crate::store::{use_board, Id};
+use pulldown_cmark::{Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+use yew::prelude::*;
+
+/// Link destinations of this form are @mentions of a person.
+const MENTION: &str = "mention:";
+
+fn parser(text: &str) -> Parser<'_> {
+    Parser::new_ext(
+        text,
+        Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TABLES | Options::ENABLE_TASKLISTS,
+    )
+}
+
+fn mentioned(dest_url: &str) -> Option<Id> {
+    dest_url.strip_prefix(MENTION)?.parse().ok()
+}
+
+/// The Markdown for an @mention, e.g. `[@Ada](mention:12)`. Rendering shows
+/// the person's current name, so renaming them later is fine.
+pub fn mention(person: &Person) -> String {
+    let name: String = person
+        .name
+        .chars()
+        .filter(|c| !matches!(c, '[' | ']'))
+        .collect();
+    format!("[@{}]({}{})", name, MENTION, person.id)
+}
+
+/// Everyone @mentioned in `text`, in order, without repeats.
+pub fn mentions(text: &str) -> Vec<Id> {
+    let mut people = Vec::new();
+    for event in parser(text) {
+        if let Event::Start(Tag::Link { dest_url, .. }) = event {
+            if let Some(person) = mentioned(&dest_url) {
+                if !people.contains(&person) {
+                    people.push(person);
+                }
+            }
+        }
+    }
+    people
+}
+
+/// The text of the first non-empty block, without any formatting. Used for
+/// one-line previews.
+pub fn plain_text(text: &str) -> String {
+    let mut line = String::new();
+    for event in parser(text) {
+        match event {
+            Event::Text(text) | Event::Code(text) => line.push_str(&text),
+            Event::SoftBreak | Event::HardBreak => line.push(' '),
+            Event::TaskListMarker(done) => line.push_str(if done { "☑ " } else { "☐ " }),
+            // Items of a tight list have no paragraph of their own, and a
+            // nested list starts inside its parent item.
+            Event::Start(Tag::List(_)) if !line.trim().is_empty() => break,
+            Event::End(
+                TagEnd::Paragraph
+                | TagEnd::Heading(_)
+                | TagEnd::CodeBlock
+                | TagEnd::TableCell
+                | TagEnd::Item,
+            ) if !line.trim().is_empty() => break,
+            _ => {}
+        }
+    }
+    line.trim().to_string()
+}
+
+/// Only these links are rendered as links; anything else, such as
+/// `javascript:`, is shown as plain text.
+fn is_safe(url: &str) -> bool {
+    let url = url.trim_start().to_ascii_lowercase();
+    ["http://", "https://", "mailto:"]
+        .iter()
+        .any(|scheme| url.starts_with(scheme))
+}
+
+fn element(tag: &'static str, class: &'static str, children: Vec<Html>) -> Html {
+    html! { <@{tag} class={class}>{ for children }</@> }
+}
+
+/// Builds the element for a finished tag.
+fn finish(tag: Tag, children: Vec<Html>, people: &[Person]) -> Html {
+    match tag {
+        Tag::Paragraph => element("p", "", children),
+        Tag::Heading { level, .. } => match level {
+            HeadingLevel::H1 => element("h3", "text-lg font-bold", children),
+            HeadingLevel::H2 => element("h4", "text-base font-bold", children),
+            _ => element("h5", "font-semibold", children),
+        },
+        Tag::BlockQuote(_) => element("blockquote", "pl-3 border-l-4 text-gray-600", children),
+        Tag::CodeBlock(_) => html! {
+            <pre class="p-2 overflow-x-auto font-mono text-xs bg-gray-100 rounded">
+                <code>{ for children }</code>
+            </pre>
+        },
+        Tag::List(Some(start)) => html! {
+            <ol class="pl-5 list-decimal" start={start.to_string()}>{ for children }</ol>
+        },
+        Tag::List(None) => element("ul", "pl-5 list-disc", children),
+        Tag::Item => element("li", "", children),
+        Tag::Table(_) => element("table", "border-collapse", children),
+        Tag::TableHead => element("tr", "font-semibold bg-gray-50", children),
+        Tag::TableRow => element("tr", "", children),
+        Tag::TableCell => element("td", "px-2 py-1 border", children),
+        Tag::Emphasis => element("em", "", children),
+        Tag::Strong => element("strong", "", children),
+        Tag::Strikethrough => element("del", "", children),
+        Tag::Link { dest_url, .. } => match mentioned(&dest_url) {
+            Some(id) => match people.iter().find(|person| person.id == id) {
+                Some(person) => html! {
+                    <span class="inline-flex items-center px-1 space-x-1 text-blue-700 bg-blue-50 rounded">
+                        <Avatar person={id} />
+                        <span>{ format!("@{}", person.name) }</span>
+                    </span>
+                },
+                None => element("span", "text-gray-500", children),
+            },
+            None if is_safe(&dest_url) => html! {
+                <a class="text-blue-600 underline" href={dest_url.to_string()} target="_blank" rel="noopener noreferrer">
+                    { for children }
+                </a>
+            },
+            None => element("span", "", children),
+        },
+        // Images would load from anywhere, so show their alt text instead.
+        Tag::Image { .. } => element("span", "italic", children),
+        _ => html! { <>{ for children }</> },
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct MarkdownProps {
+    pub text: String,
+}
+
+/// Renders Markdown as Yew elements rather than an HTML string, so raw HTML
+/// in the text shows up as text and nothing in it can run.
+#[function_component(Markdown)]
+pub fn markdown(props: &MarkdownProps) -> Html {
+    let board = use_board();
+    let mut stack: Vec<(Tag, Vec<Html>)> = Vec::new();
+    let mut blocks = Vec::new();
+
+    for event in parser(&props.text) {
+        let node = match event {
+            Event::Start(tag) => {
+                stack.push((tag, Vec::new()));
+                continue;
+            }
+            Event::End(_) => {
+                let Some((tag, children)) = stack.pop() else {
+                    continue;
+                };
+                finish(tag, children, &board.people)
+            }
+            Event::Text(text) | Event::Html(text) | Event::InlineHtml(text) => {
+                html! { { text.to_string() } }
+            }
+            Event::Code(text) => html! {
+                <code class="px-1 font-mono text-xs bg-gray-100 rounded">{ text.to_string() }</code>
+            },
+            Event::SoftBreak => html! { {" "} },
+            Event::HardBreak => html! { <br /> },
+            Event::Rule => html! { <hr /> },
+            Event::TaskListMarker(done) => html! {
+                <input class="mr-1" type="checkbox" checked={done} disabled=true />
+            },
+            Event::FootnoteReference(label) => html! { { format!("[^{}]", label) } },
+            _ => html! {},
+        };
+        match stack.last_mut() {
+            Some((_, children)) => children.push(node),
+            None => blocks.push(node),
+        }
+    }
+
+    html! {
+        <div class="space-y-2 text-sm break-words">{ for blocks }</div>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_text_strips_formatting() {
+        assert_eq!(plain_text(""), "");
+        assert_eq!(
+            plain_text("**Ship** the _new_ `build` ~~today~~"),
+            "Ship the new build today"
+        );
+        assert_eq!(plain_text("# Plan\n\nMore below"), "Plan");
+        assert_eq!(plain_text("one\ntwo  \nthree"), "one two three");
+        assert_eq!(
+            plain_text("[docs](https://example.com) for [@Ada](mention:12)"),
+            "docs for @Ada"
+        );
+    }
+
+    #[test]
+    fn plain_text_takes_the_first_block_with_text() {
+        assert_eq!(plain_text("\n\n---\n\nSecond"), "Second");
+        assert_eq!(plain_text("- [x] Draft\n- [ ] Review"), "☑ Draft");
+        assert_eq!(plain_text("- Launch\n  - Email\n  - Blog"), "Launch");
+        assert_eq!(plain_text("> quoted\n\nafter"), "quoted");
+        assert_eq!(plain_text("```\nlet x = 1;\n```"), "let x = 1;");
+        assert_eq!(plain_text("| A | B |\n|---|---|\n| 1 | 2 |"), "A");
+    }
+
+    #[test]
+    fn mentions_are_listed_once_in_order() {
+        let ada = Person::new(12, "Ada [L]".to_string(), String::new(), 0);
+        assert_eq!(mention(&ada), "[@Ada L](mention:12)");
+        let text = "[@Bo](mention:3) and [@Ada](mention:12), again [@Bo](mention:3) \
+                    and [a link](https://example.com)";
+        assert_eq!(mentions(text), vec![3, 12]);
+    }
+}
@@ -0,0 +1,292 @@
+use crate::cell::CellValue;
+use crate::column::{ColumnDef, ColumnScope};
+use crate::detail::{use_open_item, ItemRef};
+use crate::markdown::{mention, mentions};
+use crate::people::Avatar;
+use crate::store::{use_board, Board, Cells, Id};
+use crate::updates::Update;
+use web_sys::{HtmlSelectElement, HtmlTextAreaElement, KeyboardEvent};
+use yew::prelude::*;
+
+/// The longest name fragment after "@" that still offers suggestions.
+const MAX_QUERY: usize = 30;
+
+/// The "@" being typed before the cursor: its byte offset and the text after
+/// it.
+fn query_at(text: &str, cursor: usize) -> Option<(usize, &str)> {
+    let before = text.get(..cursor)?;
+    let at = before.rfind('@')?;
+    let query = &before[at + 1..];
+    let starts_word = !matches!(before[..at].chars().next_back(), Some(c) if !c.is_whitespace());
+    (starts_word && !query.contains('\n') && query.len() <= MAX_QUERY).then_some((at, query))
+}
+
+/// Everyone mentioned in an item's notes columns or its updates.
+fn mentioned_in(columns: &[ColumnDef], cells: &Cells, updates: &[Update]) -> Vec<Id> {
+    let mut people: Vec<Id> = columns
+        .iter()
+        .filter_map(|column| match column.cell(cells) {
+            CellValue::LongText(text) => Some(mentions(&text)),
+            _ => None,
+        })
+        .chain(updates.iter().map(|update| mentions(&update.body)))
+        .flatten()
+        .collect();
+    people.sort_unstable();
+    people.dedup();
+    people
+}
+
+/// Every task and subitem whose notes or updates mention `person`, with its
+/// name, in board order.
+pub fn mentioning(board: &Board, person: Id) -> Vec<(ItemRef, String)> {
+    let mut items = Vec::new();
+    for task in board.groups.iter().flat_map(|group| &group.tasks) {
+        if mentioned_in(&board.columns, &task.cells, &task.updates).contains(&person) {
+            let item = ItemRef {
+                scope: ColumnScope::Task,
+                id: task.id,
+            };
+            items.push((item, task.name.clone()));
+        }
+        for subitem in &task.subitems {
+            let people = mentioned_in(&board.subitem_columns, &subitem.cells, &subitem.updates);
+            if people.contains(&person) {
+                let item = ItemRef {
+                    scope: ColumnScope::Subitem,
+                    id: subitem.id,
+                };
+                items.push((item, format!("{} › {}", task.name, subitem.name)));
+            }
+        }
+    }
+    items
+}
+
+#[derive(Properties, PartialEq)]
+pub struct MentionTextareaProps {
+    pub value: String,
+    pub on_input: Callback<String>,
+    #[prop_or_default]
+    pub onblur: Callback<FocusEvent>,
+    #[prop_or_default]
+    pub onkeydown: Callback<KeyboardEvent>,
+    #[prop_or(3)]
+    pub rows: u32,
+    #[prop_or_default]
+    pub placeholder: AttrValue,
+    #[prop_or_default]
+    pub autofocus: bool,
+}
+
+/// A textarea that suggests people from the directory after "@" and inserts
+/// the picked person as a mention. Enter or Tab picks the first suggestion.
+#[function_component(MentionTextarea)]
+pub fn mention_textarea(props: &MentionTextareaProps) -> Html {
+    let board = use_board();
+    let textarea = use_node_ref();
+    let query = use_state(|| None::<(usize, String)>);
+
+    {
+        let textarea = textarea.clone();
+        use_effect_with(props.autofocus, move |autofocus| {
+            if *autofocus {
+                if let Some(textarea) = textarea.cast::<HtmlTextAreaElement>() {
+                    let _ = textarea.focus();
+                }
+            }
+        });
+    }
+
+    let suggestions: Vec<_> = match &*query {
+        Some((_, query)) => {
+            let query = query.to_lowercase();
+            board
+                .people
+                .iter()
+                .filter(|person| person.name.to_lowercase().contains(&query))
+                .collect()
+        }
+        None => Vec::new(),
+    };
+
+    let on_input = {
+        let on_input = props.on_input.clone();
+        let query = query.clone();
+        Callback::from(move |e: InputEvent| {
+            if let Some(input) = e.target_dyn_into::<HtmlTextAreaElement>() {
+                let text = input.value();
+                // The selection is counted in UTF-16 units; turn it into a
+                // byte offset.
+                let cursor = input.selection_start().ok().flatten().unwrap_or(0) as usize;
+                let before: Vec<u16> = text.encode_utf16().take(cursor).collect();
+                let cursor = String::from_utf16_lossy(&before).len();
+                query.set(query_at(&text, cursor).map(|(at, query)| (at, query.to_string())));
+                on_input.emit(text);
+            }
+        })
+    };
+
+    let pick = {
+        let board = board.clone();
+        let on_input = props.on_input.clone();
+        let query = query.clone();
+        let textarea = textarea.clone();
+        Callback::from(move |person: Id| {
+            let (Some((at, typed)), Some(person), Some(input)) = (
+                (*query).clone(),
+                board.person(person),
+                textarea.cast::<HtmlTextAreaElement>(),
+            ) else {
+                return;
+            };
+            let text = input.value();
+            let end = (at + 1 + typed.len()).min(text.len());
+            let inserted = format!("{} ", mention(person));
+            let text = format!("{}{}{}", &text[..at], inserted, &text[end..]);
+            let cursor = text[..at + inserted.len()].encode_utf16().count() as u32;
+            input.set_value(&text);
+            let _ = input.set_selection_range(cursor, cursor);
+            query.set(None);
+            on_input.emit(text);
+        })
+    };
+
+    let on_keydown = {
+        let onkeydown = props.onkeydown.clone();
+        let query = query.clone();
+        let pick = pick.clone();
+        let first = suggestions.first().map(|person| person.id);
+        Callback::from(move |e: KeyboardEvent| {
+            match (e.key().as_str(), first) {
+                ("Enter" | "Tab", Some(first)) => {
+                    e.prevent_default();
+                    pick.emit(first);
+                }
+                ("Escape", Some(_)) => {
+                    // Close the suggestions, not the panel around them.
+                    e.stop_propagation();
+                    query.set(None);
+                }
+                _ => onkeydown.emit(e),
+            }
+        })
+    };
+
+    let on_blur = {
+        let onblur = props.onblur.clone();
+        let query = query.clone();
+        Callback::from(move |e: FocusEvent| {
+            query.set(None);
+            onblur.emit(e);
+        })
+    };
+
+    html! {
+        <div class="relative">
+            <textarea
+                ref={textarea}
+                class="w-full p-2 text-sm border rounded"
+                rows={props.rows.to_string()}
+                placeholder={props.placeholder.clone()}
+                value={props.value.clone()}
+                oninput={on_input}
+                onkeydown={on_keydown}
+                onblur={on_blur}
+            />
+            { if suggestions.is_empty() {
+                html! {}
+            } else {
+                html! {
+                    <ul class="absolute z-10 w-56 p-1 bg-white border rounded-lg shadow-lg">
+                        { for suggestions.iter().map(|person| {
+                            let id = person.id;
+                            // Mouse down rather than click, so the textarea
+                            // keeps focus and does not save on blur first.
+                            let on_mouse_down = {
+                                let pick = pick.clone();
+                                Callback::from(move |e: MouseEvent| {
+                                    e.prevent_default();
+                                    pick.emit(id);
+                                })
+                            };
+                            html! {
+                                <li
+                                    key={id}
+                                    class="flex items-center px-2 py-1 space-x-2 text-sm rounded cursor-pointer hover:bg-gray-100"
+                                    onmousedown={on_mouse_down}
+                                >
+                                    <Avatar person={id} />
+                                    <span>{ &person.name }</span>
+                                </li>
+                            }
+                        }) }
+                    </ul>
+                }
+            }}
+        </div>
+    }
+}
+
+/// Pick a person to list every task and subitem that mentions them in its
+/// notes or updates. Clicking one opens it.
+#[function_component(MentionsPanel)]
+pub fn mentions_panel() -> Html {
+    let board = use_board();
+    let open_item = use_open_item();
+    let person = use_state(|| board.people.first().map(|person| person.id));
+
+    let on_person = {
+        let board = board.clone();
+        let person = person.clone();
+        Callback::from(move |e: Event| {
+            if let Some(select) = e.target_dyn_into::<HtmlSelectElement>() {
+                let index = select.selected_index() as usize;
+                person.set(board.people.get(index).map(|person| person.id));
+            }
+        })
+    };
+
+    let items = person
+        .map(|person| mentioning(&board, person))
+        .unwrap_or_default();
+
+    html! {
+        <div class="mt-4 p-4 border rounded-lg bg-gray-50">
+            <div class="flex items-center space-x-2">
+                <span class="font-semibold">{"Mentions of"}</span>
+                <select class="p-1 text-sm border rounded" onchange={on_person}>
+                    { for board.people.iter().map(|each| html! {
+                        <option selected={*person == Some(each.id)}>{ &each.name }</option>
+                    }) }
+                </select>
+            </div>
+            { if items.is_empty() {
+                html! { <p class="mt-2 text-sm text-gray-500">{"Nobody has mentioned them yet."}</p> }
+            } else {
+                html! {
+                    <ul class="mt-2">
+                        { for items.into_iter().map(|(item, name)| {
+                            let on_open = {
+                                let open_item = open_item.clone();
+                                Callback::from(move |_| open_item.set(Some(item)))
+                            };
+                            let kind = match item.scope {
+                                ColumnScope::Task => "Task",
+                                ColumnScope::Subitem => "Subitem",
+                            };
+                            html! {
+                                <li key={item.id} class="py-1 text-sm">
+                                    <button class="text-left hover:text-blue-600" onclick={on_open}>
+                                        <span class="mr-2 text-gray-500">{ kind }</span>
+                                        { name }
+                                    </button>
+                                </li>
+                            }
+                        }) }
+                    </ul>
+                }
+            }}
+        </div>
+    }
+}
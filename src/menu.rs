@@ -0,0 +1,85 @@
+use yew::prelude::*;
+
+#[derive(Clone, PartialEq)]
+pub struct MenuItem {
+    pub label: String,
+    pub on_select: Callback<()>,
+}
+
+impl MenuItem {
+    pub fn new(label: &str, on_select: Callback<()>) -> Self {
+        Self {
+            label: label.to_string(),
+            on_select,
+        }
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct RowMenuProps {
+    pub items: Vec<MenuItem>,
+}
+
+/// The "..." button shown on groups, tasks and subitems. Right-clicking the
+/// button opens it too.
+#[function_component(RowMenu)]
+pub fn row_menu(props: &RowMenuProps) -> Html {
+    let is_open = use_state(|| false);
+
+    let toggle = {
+        let is_open = is_open.clone();
+        Callback::from(move |_| {
+            is_open.set(!*is_open);
+        })
+    };
+
+    let on_context_menu = {
+        let is_open = is_open.clone();
+        Callback::from(move |e: MouseEvent| {
+            e.prevent_default();
+            is_open.set(true);
+        })
+    };
+
+    let close = {
+        let is_open = is_open.clone();
+        Callback::from(move |_| {
+            is_open.set(false);
+        })
+    };
+
+    html! {
+        <div class="relative inline-block" onmouseleave={close}>
+            <button
+                class="px-2 text-gray-500 hover:text-gray-900 focus:outline-none"
+                onclick={toggle}
+                oncontextmenu={on_context_menu}
+            >
+                {"..."}
+            </button>
+            { if *is_open {
+                html! {
+                    <ul class="absolute right-0 z-10 mt-1 w-32 bg-white border rounded-lg shadow-lg text-sm">
+                        { for props.items.iter().map(|item| {
+                            let is_open = is_open.clone();
+                            let on_select = item.on_select.clone();
+                            html! {
+                                <li
+                                    class="px-3 py-1 cursor-pointer hover:bg-gray-100"
+                                    onclick={Callback::from(move |_| {
+                                        is_open.set(false);
+                                        on_select.emit(());
+                                    })}
+                                >
+                                    { &item.label }
+                                </li>
+                            }
+                        }) }
+                    </ul>
+                }
+            } else {
+                html! {}
+            }}
+        </div>
+    }
+}
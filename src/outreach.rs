@@ -0,0 +1,218 @@
+use crate::cell::{CellKind, CellValue, Contact};
+use crate::column::ColumnDef;
+use crate::people::Avatar;
+use crate::store::{use_board, Id, SubitemData};
+use chrono::{DateTime, Local, Utc};
+use std::fmt;
+use web_sys::HtmlSelectElement;
+use yew::prelude::*;
+
+/// How many of the people contacted have responded.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct Progress {
+    pub responded: usize,
+    pub total: usize,
+}
+
+impl Progress {
+    pub fn of(contacts: &[Contact]) -> Self {
+        Self {
+            responded: contacts
+                .iter()
+                .filter(|contact| contact.responded.is_some())
+                .count(),
+            total: contacts.len(),
+        }
+    }
+}
+
+impl fmt::Display for Progress {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}/{} responded", self.responded, self.total)
+    }
+}
+
+/// Outreach across every subitem of a task, or `None` if none of them has
+/// contacted anyone.
+pub fn rollup(columns: &[ColumnDef], subitems: &[SubitemData]) -> Option<Progress> {
+    let mut progress = Progress::default();
+    for column in columns
+        .iter()
+        .filter(|column| column.kind == CellKind::Outreach)
+    {
+        for subitem in subitems {
+            if let CellValue::Outreach(contacts) = column.cell(&subitem.cells) {
+                let cell = Progress::of(&contacts);
+                progress.responded += cell.responded;
+                progress.total += cell.total;
+            }
+        }
+    }
+    (progress.total > 0).then_some(progress)
+}
+
+fn timestamp(at: DateTime<Utc>) -> String {
+    at.with_timezone(&Local).format("%b %-d, %H:%M").to_string()
+}
+
+/// e.g. "Sent Aug 3, 14:05 · Responded Aug 4, 09:30".
+fn history(contact: &Contact) -> String {
+    let mut steps = Vec::new();
+    if let Some(sent) = contact.sent {
+        steps.push(format!("Sent {}", timestamp(sent)));
+    }
+    if let Some(responded) = contact.responded {
+        steps.push(format!("Responded {}", timestamp(responded)));
+    }
+    if steps.is_empty() {
+        "Not contacted yet".to_string()
+    } else {
+        steps.join(" · ")
+    }
+}
+
+/// A click handler committing a changed copy of the whole list.
+fn edit(
+    props: &OutreachCellProps,
+    change: impl Fn(&mut Vec<Contact>) + 'static,
+) -> Callback<MouseEvent> {
+    let on_commit = props.on_commit.clone();
+    let contacts = props.value.clone();
+    Callback::from(move |_| {
+        let mut contacts = contacts.clone();
+        change(&mut contacts);
+        on_commit.emit(CellValue::Outreach(contacts));
+    })
+}
+
+#[derive(Properties, PartialEq)]
+pub struct OutreachCellProps {
+    pub value: Vec<Contact>,
+    pub on_commit: Callback<CellValue>,
+}
+
+/// An outreach cell: "3/5 responded", opening a popover where people are
+/// added and marked as sent or responded.
+#[function_component(OutreachCell)]
+pub fn outreach_cell(props: &OutreachCellProps) -> Html {
+    let board = use_board();
+    let is_open = use_state(|| false);
+
+    let toggle = {
+        let is_open = is_open.clone();
+        Callback::from(move |_| {
+            is_open.set(!*is_open);
+        })
+    };
+
+    let close = {
+        let is_open = is_open.clone();
+        Callback::from(move |_| {
+            is_open.set(false);
+        })
+    };
+
+    let uncontacted: Vec<Id> = board
+        .people
+        .iter()
+        .map(|person| person.id)
+        .filter(|id| !props.value.iter().any(|contact| contact.person == *id))
+        .collect();
+
+    let on_add = {
+        let on_commit = props.on_commit.clone();
+        let contacts = props.value.clone();
+        let uncontacted = uncontacted.clone();
+        Callback::from(move |e: Event| {
+            if let Some(select) = e.target_dyn_into::<HtmlSelectElement>() {
+                let index = select.selected_index() as usize;
+                if let Some(person) = index
+                    .checked_sub(1)
+                    .and_then(|index| uncontacted.get(index))
+                {
+                    let mut contacts = contacts.clone();
+                    contacts.push(Contact::new(*person));
+                    on_commit.emit(CellValue::Outreach(contacts));
+                }
+            }
+        })
+    };
+
+    let progress = Progress::of(&props.value);
+
+    html! {
+        <div class="relative" onmouseleave={close}>
+            <button class="w-full rounded px-2 py-1 text-sm text-left text-gray-700 hover:bg-gray-100" onclick={toggle}>
+                { if progress.total == 0 { "-".to_string() } else { progress.to_string() } }
+            </button>
+            { if *is_open {
+                html! {
+                    <div class="absolute z-10 mt-1 w-80 p-2 bg-white border rounded-lg shadow-lg text-sm">
+                        <ul class="space-y-1">
+                            { for props.value.iter().enumerate().map(|(index, contact)| {
+                                let name = board
+                                    .person(contact.person)
+                                    .map(|person| person.name.clone())
+                                    .unwrap_or_else(|| "Removed person".to_string());
+                                let on_sent = edit(props, move |contacts| {
+                                    let contact = &mut contacts[index];
+                                    if contact.sent.is_some() {
+                                        contact.sent = None;
+                                        contact.responded = None;
+                                    } else {
+                                        contact.sent = Some(Utc::now());
+                                    }
+                                });
+                                let on_responded = edit(props, move |contacts| {
+                                    let contact = &mut contacts[index];
+                                    if contact.responded.is_some() {
+                                        contact.responded = None;
+                                    } else {
+                                        let now = Utc::now();
+                                        contact.sent.get_or_insert(now);
+                                        contact.responded = Some(now);
+                                    }
+                                });
+                                let on_remove = edit(props, move |contacts| {
+                                    contacts.remove(index);
+                                });
+                                html! {
+                                    <li key={format!("{}-{}", index, contact.person)} class="flex items-center space-x-2">
+                                        <Avatar person={contact.person} />
+                                        <div class="flex flex-col flex-1 min-w-0">
+                                            <span class="truncate">{ name }</span>
+                                            <span class="text-xs text-gray-500">{ history(contact) }</span>
+                                        </div>
+                                        <label>
+                                            <input class="mr-1" type="checkbox" checked={contact.sent.is_some()} onclick={on_sent} />
+                                            {"Sent"}
+                                        </label>
+                                        <label>
+                                            <input class="mr-1" type="checkbox" checked={contact.responded.is_some()} onclick={on_responded} />
+                                            {"Responded"}
+                                        </label>
+                                        <button class="text-red-600" onclick={on_remove}>{"×"}</button>
+                                    </li>
+                                }
+                            }) }
+                        </ul>
+                        { if uncontacted.is_empty() {
+                            html! {}
+                        } else {
+                            html! {
+                                <select class="mt-2 w-full border rounded p-1" onchange={on_add}>
+                                    <option selected=true>{"Add person..."}</option>
+                                    { for uncontacted.iter().filter_map(|id| board.person(*id)).map(|person| html! {
+                                        <option>{ &person.name }</option>
+                                    }) }
+                                </select>
+                            }
+                        }}
+                    </div>
+                }
+            } else {
+                html! {}
+            }}
+        </div>
+    }
+}
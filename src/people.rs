@@ -0,0 +1,314 @@
+use crate::cell::CellValue;
+use crate::column::ColumnDef;
+use crate::status::{cycle_color, LABEL_COLORS};
+use crate::store::{use_board, BoardAction, Cells, Id};
+use serde::{Deserialize, Serialize};
+use web_sys::{HtmlInputElement, HtmlSelectElement, KeyboardEvent};
+use yew::prelude::*;
+
+/// Someone in the board's people directory, who person cells can list.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct Person {
+    pub id: Id,
+    pub name: String,
+    /// Shown on the person's avatar, e.g. "JD".
+    pub initials: String,
+    /// A Tailwind color name, one of [`LABEL_COLORS`].
+    pub color: String,
+    #[serde(default)]
+    pub email: String,
+}
+
+impl Person {
+    /// A new directory entry, with initials worked out from the name and the
+    /// `index`th avatar color.
+    pub fn new(id: Id, name: String, email: String, index: usize) -> Self {
+        Self {
+            id,
+            initials: initials(&name),
+            color: cycle_color(index),
+            name,
+            email,
+        }
+    }
+}
+
+/// The first letter of up to two words, e.g. "Jane Doe" gives "JD".
+pub fn initials(name: &str) -> String {
+    name.split_whitespace()
+        .filter_map(|word| word.chars().next())
+        .take(2)
+        .flat_map(char::to_uppercase)
+        .collect()
+}
+
+/// Whether any person cell in `cells` lists `person`, or any outreach cell
+/// has them as a contact.
+pub fn is_assigned(columns: &[ColumnDef], cells: &Cells, person: Id) -> bool {
+    columns.iter().any(|column| match column.cell(cells) {
+        CellValue::Person(people) => people.contains(&person),
+        CellValue::Outreach(contacts) => contacts.iter().any(|contact| contact.person == person),
+        _ => false,
+    })
+}
+
+#[derive(Properties, PartialEq)]
+pub struct AvatarProps {
+    pub person: Id,
+}
+
+/// A small colored circle with a person's initials.
+#[function_component(Avatar)]
+pub fn avatar(props: &AvatarProps) -> Html {
+    let board = use_board();
+    let Some(person) = board.person(props.person) else {
+        return html! {};
+    };
+
+    html! {
+        <span
+            class={format!("inline-flex items-center justify-center w-6 h-6 rounded-full text-xs font-semibold text-white bg-{}-500", person.color)}
+            title={person.name.clone()}
+        >
+            { &person.initials }
+        </span>
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct PersonPickerProps {
+    pub value: Vec<Id>,
+    pub on_commit: Callback<CellValue>,
+}
+
+/// A person cell: avatar chips that open a popover for picking people from
+/// the directory.
+#[function_component(PersonPicker)]
+pub fn person_picker(props: &PersonPickerProps) -> Html {
+    let board = use_board();
+    let is_open = use_state(|| false);
+
+    let toggle = {
+        let is_open = is_open.clone();
+        Callback::from(move |_| {
+            is_open.set(!*is_open);
+        })
+    };
+
+    let close = {
+        let is_open = is_open.clone();
+        Callback::from(move |_| {
+            is_open.set(false);
+        })
+    };
+
+    let pick = |id: Id| {
+        let on_commit = props.on_commit.clone();
+        let mut people = props.value.clone();
+        if let Some(index) = people.iter().position(|person| *person == id) {
+            people.remove(index);
+        } else {
+            people.push(id);
+        }
+        Callback::from(move |_| {
+            on_commit.emit(CellValue::Person(people.clone()));
+        })
+    };
+
+    let assigned: Vec<Id> = props
+        .value
+        .iter()
+        .copied()
+        .filter(|id| board.person(*id).is_some())
+        .collect();
+
+    html! {
+        <div class="relative" onmouseleave={close}>
+            <button class="flex items-center w-full min-h-8 px-1 py-1 -space-x-1 text-sm text-gray-500 rounded hover:bg-gray-100" onclick={toggle}>
+                { if assigned.is_empty() {
+                    html! { "-" }
+                } else {
+                    html! {
+                        { for assigned.iter().map(|id| html! { <Avatar key={*id} person={*id} /> }) }
+                    }
+                }}
+            </button>
+            { if *is_open {
+                html! {
+                    <ul class="absolute z-10 mt-1 w-56 p-1 bg-white border rounded-lg shadow-lg space-y-1">
+                        { for board.people.iter().map(|person| html! {
+                            <li
+                                key={person.id}
+                                class={classes!("flex", "items-center", "space-x-2", "rounded", "px-2", "py-1", "text-sm", "cursor-pointer", "hover:bg-gray-100", props.value.contains(&person.id).then_some("font-semibold"))}
+                                onclick={pick(person.id)}
+                            >
+                                <Avatar person={person.id} />
+                                <span class="flex-1">{ &person.name }</span>
+                                { if props.value.contains(&person.id) { "✓" } else { "" } }
+                            </li>
+                        }) }
+                        { if board.people.is_empty() {
+                            html! { <li class="px-2 py-1 text-sm text-gray-500">{"Add people in the People panel"}</li> }
+                        } else {
+                            html! {}
+                        }}
+                    </ul>
+                }
+            } else {
+                html! {}
+            }}
+        </div>
+    }
+}
+
+/// Lets the user add, edit and remove the people in the board's directory.
+#[function_component(PeoplePanel)]
+pub fn people_panel() -> Html {
+    let board = use_board();
+    let new_name = use_state(String::new);
+    let new_email = use_state(String::new);
+
+    let on_new_name = {
+        let new_name = new_name.clone();
+        Callback::from(move |e: InputEvent| {
+            if let Some(input) = e.target_dyn_into::<HtmlInputElement>() {
+                new_name.set(input.value());
+            }
+        })
+    };
+
+    let on_new_email = {
+        let new_email = new_email.clone();
+        Callback::from(move |e: InputEvent| {
+            if let Some(input) = e.target_dyn_into::<HtmlInputElement>() {
+                new_email.set(input.value());
+            }
+        })
+    };
+
+    let on_add = {
+        let board = board.clone();
+        let new_name = new_name.clone();
+        let new_email = new_email.clone();
+        Callback::from(move |_| {
+            let name = new_name.trim();
+            if !name.is_empty() {
+                board.dispatch(BoardAction::AddPerson {
+                    name: name.to_string(),
+                    email: new_email.trim().to_string(),
+                });
+                new_name.set(String::new());
+                new_email.set(String::new());
+            }
+        })
+    };
+
+    html! {
+        <div class="mt-4 p-4 border rounded-lg bg-gray-50">
+            <span class="font-semibold">{"People"}</span>
+            <ul class="mt-2 space-y-2">
+                { for board.people.iter().map(|person| {
+                    let id = person.id;
+                    let on_rename = {
+                        let board = board.clone();
+                        Callback::from(move |e: KeyboardEvent| {
+                            if e.key() == "Enter" {
+                                if let Some(input) = e.target_dyn_into::<HtmlInputElement>() {
+                                    board.dispatch(BoardAction::RenamePerson { person: id, name: input.value() });
+                                }
+                            }
+                        })
+                    };
+                    let on_initials = {
+                        let board = board.clone();
+                        Callback::from(move |e: KeyboardEvent| {
+                            if e.key() == "Enter" {
+                                if let Some(input) = e.target_dyn_into::<HtmlInputElement>() {
+                                    board.dispatch(BoardAction::SetPersonInitials { person: id, initials: input.value().trim().to_uppercase() });
+                                }
+                            }
+                        })
+                    };
+                    let on_email = {
+                        let board = board.clone();
+                        Callback::from(move |e: KeyboardEvent| {
+                            if e.key() == "Enter" {
+                                if let Some(input) = e.target_dyn_into::<HtmlInputElement>() {
+                                    board.dispatch(BoardAction::SetPersonEmail { person: id, email: input.value().trim().to_string() });
+                                }
+                            }
+                        })
+                    };
+                    let on_color = {
+                        let board = board.clone();
+                        Callback::from(move |e: Event| {
+                            if let Some(select) = e.target_dyn_into::<HtmlSelectElement>() {
+                                board.dispatch(BoardAction::SetPersonColor { person: id, color: select.value() });
+                            }
+                        })
+                    };
+                    let on_remove = {
+                        let board = board.clone();
+                        Callback::from(move |_| board.dispatch(BoardAction::RemovePerson { person: id }))
+                    };
+                    let is_me = board.me == Some(id);
+                    let on_me = {
+                        let board = board.clone();
+                        Callback::from(move |_| {
+                            board.dispatch(BoardAction::SetMe { person: (!is_me).then_some(id) })
+                        })
+                    };
+                    html! {
+                        <li key={id} class="flex items-center space-x-2 text-sm">
+                            <Avatar person={id} />
+                            <input class="border rounded p-1 w-48" type="text" value={person.name.clone()} onkeydown={on_rename} />
+                            <input class="border rounded p-1 w-12" type="text" maxlength="3" value={person.initials.clone()} onkeydown={on_initials} title="Initials" />
+                            <input class="border rounded p-1 w-56" type="email" placeholder="Email" value={person.email.clone()} onkeydown={on_email} />
+                            <select class="border rounded p-1" onchange={on_color}>
+                                { for LABEL_COLORS.into_iter().map(|color| html! {
+                                    <option value={color} selected={person.color == color}>{ color }</option>
+                                }) }
+                            </select>
+                            <label class="flex items-center space-x-1" title="Used by filters like \"is me\"">
+                                <input type="checkbox" checked={is_me} onchange={on_me} />
+                                <span>{"This is me"}</span>
+                            </label>
+                            <button class="text-red-600" onclick={on_remove}>{"Remove"}</button>
+                        </li>
+                    }
+                }) }
+            </ul>
+            <div class="flex items-center mt-3 space-x-2 text-sm">
+                <input class="border rounded p-1 w-48" type="text" placeholder="New person" value={(*new_name).clone()} oninput={on_new_name} />
+                <input class="border rounded p-1 w-56" type="email" placeholder="Email" value={(*new_email).clone()} oninput={on_new_email} />
+                <button class="p-1 px-2 bg-blue-500 text-white rounded" onclick={on_add}>{"Add person"}</button>
+            </div>
+        </div>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cell::{CellKind, Contact};
+
+    #[test]
+    fn outreach_contacts_count_as_assigned() {
+        let columns = [
+            ColumnDef::new(1, "Owner".to_string(), CellKind::Person),
+            ColumnDef::new(2, "Outreach".to_string(), CellKind::Outreach),
+        ];
+        let contact = Contact {
+            person: 8,
+            sent: None,
+            responded: None,
+        };
+        let cells = Cells::from([
+            (1, CellValue::Person(vec![7])),
+            (2, CellValue::Outreach(vec![contact])),
+        ]);
+        assert!(is_assigned(&columns, &cells, 7));
+        assert!(is_assigned(&columns, &cells, 8));
+        assert!(!is_assigned(&columns, &cells, 9));
+    }
+}
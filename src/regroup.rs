@@ -0,0 +1,207 @@
+use crate::cell::{CellKind, CellValue};
+use crate::column::ColumnDef;
+use crate::status::cycle_color;
+use crate::store::{Board, Id};
+use chrono::Datelike;
+use std::collections::BTreeMap;
+
+/// Tasks from across the board that share a value in the column the board
+/// is grouped by. Shown like a group, but the tasks stay where they are.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Bucket {
+    /// Unique among the buckets of one column, for use as a key.
+    pub key: String,
+    pub name: String,
+    pub color: String,
+    pub tasks: Vec<Id>,
+}
+
+/// Whether the board can be grouped by columns of this kind.
+pub fn can_group_by(kind: CellKind) -> bool {
+    matches!(
+        kind,
+        CellKind::Status | CellKind::Select | CellKind::Person | CellKind::Date
+    )
+}
+
+/// Where a cell goes: for each bucket it belongs in, the bucket's place in
+/// the order, name and color. A task with several people goes in each of
+/// their buckets. Empty for the empty bucket.
+fn places(board: &Board, column: &ColumnDef, value: &CellValue) -> Vec<(i64, String, String)> {
+    match value {
+        CellValue::Status(status) => board
+            .status_labels
+            .iter()
+            .enumerate()
+            .filter(|(_, label)| Some(label.id) == *status)
+            .map(|(index, label)| (index as i64, label.name.clone(), label.color.clone()))
+            .collect(),
+        CellValue::Select(Some(option)) => {
+            let index = column
+                .options
+                .iter()
+                .position(|each| each == option)
+                .unwrap_or(column.options.len());
+            vec![(index as i64, option.clone(), cycle_color(index))]
+        }
+        CellValue::Person(people) => board
+            .people
+            .iter()
+            .enumerate()
+            .filter(|(_, person)| people.contains(&person.id))
+            .map(|(index, person)| (index as i64, person.name.clone(), person.color.clone()))
+            .collect(),
+        CellValue::Date(Some(date)) => {
+            // Counted in i64 so that months before year 0 still sort first.
+            let month = i64::from(date.year()) * 12 + i64::from(date.month0());
+            vec![(
+                month,
+                date.format("%B %Y").to_string(),
+                cycle_color(date.month0() as usize),
+            )]
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Every task on the board sorted into buckets by `column`, in the
+/// column's order: labels and options as listed, people as in the
+/// directory, months by date. Tasks with nothing set come last.
+pub fn buckets(board: &Board, column: &ColumnDef) -> Vec<Bucket> {
+    let mut buckets: BTreeMap<(i64, String), Bucket> = BTreeMap::new();
+    for task in board.groups.iter().flat_map(|group| group.tasks.iter()) {
+        let mut places = places(board, column, &column.cell(&task.cells));
+        if places.is_empty() {
+            places.push((i64::MAX, format!("No {}", column.name), "gray".to_string()));
+        }
+        for (order, name, color) in places {
+            let key = format!("{} {}", order, name);
+            buckets
+                .entry((order, name.clone()))
+                .or_insert_with(|| Bucket {
+                    key,
+                    name,
+                    color,
+                    tasks: Vec::new(),
+                })
+                .tasks
+                .push(task.id);
+        }
+    }
+    buckets.into_values().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::column::ColumnScope;
+    use crate::store::TaskData;
+    use chrono::NaiveDate;
+
+    /// The buckets the board's tasks fall into by the column `column`.
+    fn buckets_by(board: &Board, column: Id) -> Vec<Bucket> {
+        let column = board.columns.iter().find(|each| each.id == column).unwrap();
+        buckets(board, column)
+    }
+
+    /// Puts tasks with these values for `column` into the board's groups,
+    /// alternating between them, with ids from 1000.
+    fn fill(board: &mut Board, column: Id, values: Vec<Option<CellValue>>) {
+        for (index, value) in values.into_iter().enumerate() {
+            let cells = value.map(|value| (column, value)).into_iter().collect();
+            let group = index % board.groups.len();
+            board.groups[group].tasks.push(TaskData::new(
+                1000 + index as Id,
+                format!("Task {}", index),
+                cells,
+            ));
+        }
+    }
+
+    fn summary(buckets: &[Bucket]) -> Vec<(&str, Vec<Id>)> {
+        buckets
+            .iter()
+            .map(|bucket| (bucket.name.as_str(), bucket.tasks.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn options_go_in_listed_order_and_empty_last() {
+        let mut board = Board::default();
+        let area = board.column_of_kind(ColumnScope::Task, CellKind::Select);
+        let option = |name: &str| Some(CellValue::Select(Some(name.to_string())));
+        fill(
+            &mut board,
+            area,
+            vec![
+                option("Area 3"),
+                Some(CellValue::Select(None)),
+                option("Retired"),
+                option("Area 1"),
+                option("Area 3"),
+            ],
+        );
+        let buckets = buckets_by(&board, area);
+        assert_eq!(
+            summary(&buckets),
+            vec![
+                ("Area 1", vec![1003]),
+                ("Area 3", vec![1000, 1004]),
+                ("Retired", vec![1002]),
+                ("No Area", vec![1001]),
+            ]
+        );
+        assert_eq!(buckets[0].color, cycle_color(0));
+        assert_eq!(buckets[3].color, "gray");
+    }
+
+    #[test]
+    fn people_and_months_get_their_own_buckets() {
+        let mut board = Board::default();
+        let owner = board.column_of_kind(ColumnScope::Task, CellKind::Person);
+        let people: Vec<Id> = board.people.iter().map(|person| person.id).collect();
+        fill(
+            &mut board,
+            owner,
+            vec![
+                Some(CellValue::Person(vec![people[1]])),
+                Some(CellValue::Person(vec![people[0], people[1]])),
+                Some(CellValue::Person(vec![])),
+                Some(CellValue::Person(vec![people[0]])),
+            ],
+        );
+        assert_eq!(
+            summary(&buckets_by(&board, owner)),
+            vec![
+                ("Owner 1", vec![1001, 1003]),
+                ("Person 1", vec![1000, 1001]),
+                ("No Project Owner", vec![1002]),
+            ]
+        );
+
+        let mut board = Board::default();
+        let date = board.column_of_kind(ColumnScope::Task, CellKind::Date);
+        let day =
+            |year, month, day| Some(CellValue::Date(NaiveDate::from_ymd_opt(year, month, day)));
+        fill(
+            &mut board,
+            date,
+            vec![
+                day(2025, 1, 3),
+                day(2024, 12, 31),
+                day(2025, 1, 28),
+                Some(CellValue::Date(None)),
+                day(-1, 6, 1),
+            ],
+        );
+        assert_eq!(
+            summary(&buckets_by(&board, date)),
+            vec![
+                ("June -0001", vec![1004]),
+                ("December 2024", vec![1001]),
+                ("January 2025", vec![1000, 1002]),
+                ("No Date", vec![1003]),
+            ]
+        );
+    }
+}
@@ -0,0 +1,852 @@
+use crate::cell::{CellKind, CellValue, Money};
+use crate::column::{ColumnDef, ColumnScope};
+use crate::date::today;
+use crate::filter::{cell_text, find_all};
+use crate::store::{use_board, Board, Id, SubitemData, TaskData};
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use std::cmp::Ordering;
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+use yew::prelude::*;
+
+/// How a list of conditions, or of condition groups, combines.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum Join {
+    /// AND
+    #[default]
+    All,
+    /// OR
+    Any,
+}
+
+impl Join {
+    pub fn label(self) -> &'static str {
+        match self {
+            Join::All => "all",
+            Join::Any => "any",
+        }
+    }
+
+    fn combine(self, mut results: impl Iterator<Item = bool>) -> bool {
+        match self {
+            Join::All => results.all(|result| result),
+            Join::Any => results.any(|result| result),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Operator {
+    Is,
+    IsNot,
+    Contains,
+    DoesNotContain,
+    Above,
+    Below,
+    AtLeast,
+    AtMost,
+    IsEmpty,
+    IsNotEmpty,
+}
+
+impl Operator {
+    /// The operators that make sense for a kind of column, default first.
+    pub fn choices(kind: CellKind) -> &'static [Operator] {
+        use Operator::*;
+        match kind {
+            CellKind::Status | CellKind::Select => &[Is, IsNot, IsEmpty, IsNotEmpty],
+            CellKind::Person | CellKind::Outreach | CellKind::LongText | CellKind::Files => {
+                &[Contains, DoesNotContain, IsEmpty, IsNotEmpty]
+            }
+            CellKind::Money => &[
+                Above, Below, AtLeast, AtMost, Is, IsNot, IsEmpty, IsNotEmpty,
+            ],
+            CellKind::Date | CellKind::Timeline => &[
+                Below, Above, AtMost, AtLeast, Is, IsNot, IsEmpty, IsNotEmpty,
+            ],
+        }
+    }
+
+    pub fn label(self, kind: CellKind) -> &'static str {
+        let people = matches!(kind, CellKind::Person | CellKind::Outreach);
+        let dates = matches!(kind, CellKind::Date | CellKind::Timeline);
+        let spans = kind == CellKind::Timeline;
+        match self {
+            Operator::Is if spans => "includes",
+            Operator::IsNot if spans => "does not include",
+            Operator::Is => "is",
+            Operator::IsNot => "is not",
+            Operator::Contains if people => "includes",
+            Operator::Contains => "contains",
+            Operator::DoesNotContain if people => "does not include",
+            Operator::DoesNotContain => "does not contain",
+            Operator::Above if dates => "is after",
+            Operator::Above => "is greater than",
+            Operator::Below if dates => "is before",
+            Operator::Below => "is less than",
+            Operator::AtLeast if dates => "is on or after",
+            Operator::AtLeast => "is at least",
+            Operator::AtMost if dates => "is on or before",
+            Operator::AtMost => "is at most",
+            Operator::IsEmpty => "is empty",
+            Operator::IsNotEmpty => "is not empty",
+        }
+    }
+
+    fn takes_operand(self) -> bool {
+        !matches!(self, Operator::IsEmpty | Operator::IsNotEmpty)
+    }
+}
+
+/// A date a condition compares against, kept relative so that "before next
+/// Friday" stays true to its name as days go by.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DateOperand {
+    Today,
+    Tomorrow,
+    Yesterday,
+    /// The first such weekday after today.
+    Next(Weekday),
+    On(NaiveDate),
+}
+
+impl DateOperand {
+    /// The relative dates offered in the filter builder.
+    pub const PRESETS: [DateOperand; 10] = [
+        DateOperand::Today,
+        DateOperand::Tomorrow,
+        DateOperand::Yesterday,
+        DateOperand::Next(Weekday::Mon),
+        DateOperand::Next(Weekday::Tue),
+        DateOperand::Next(Weekday::Wed),
+        DateOperand::Next(Weekday::Thu),
+        DateOperand::Next(Weekday::Fri),
+        DateOperand::Next(Weekday::Sat),
+        DateOperand::Next(Weekday::Sun),
+    ];
+
+    pub fn resolve(self, today: NaiveDate) -> NaiveDate {
+        match self {
+            DateOperand::Today => today,
+            DateOperand::Tomorrow => today + Duration::days(1),
+            DateOperand::Yesterday => today - Duration::days(1),
+            DateOperand::Next(weekday) => {
+                let days = (weekday.num_days_from_monday() + 7
+                    - today.weekday().num_days_from_monday())
+                    % 7;
+                today + Duration::days(if days == 0 { 7 } else { days.into() })
+            }
+            DateOperand::On(date) => date,
+        }
+    }
+
+    pub fn label(self) -> String {
+        match self {
+            DateOperand::Today => "today".to_string(),
+            DateOperand::Tomorrow => "tomorrow".to_string(),
+            DateOperand::Yesterday => "yesterday".to_string(),
+            DateOperand::Next(weekday) => format!("next {}", weekday_name(weekday)),
+            DateOperand::On(date) => date.format("%b %-d, %Y").to_string(),
+        }
+    }
+}
+
+fn weekday_name(weekday: Weekday) -> &'static str {
+    match weekday {
+        Weekday::Mon => "Monday",
+        Weekday::Tue => "Tuesday",
+        Weekday::Wed => "Wednesday",
+        Weekday::Thu => "Thursday",
+        Weekday::Fri => "Friday",
+        Weekday::Sat => "Saturday",
+        Weekday::Sun => "Sunday",
+    }
+}
+
+/// What a condition compares the cell with.
+#[derive(Clone, PartialEq, Debug)]
+pub enum Operand {
+    /// Nothing picked yet; the condition is left out until it is.
+    None,
+    Label(Id),
+    Option(String),
+    Person(Id),
+    /// Whoever [`Board::me`] is.
+    Me,
+    Text(String),
+    Money(Money),
+    Date(DateOperand),
+}
+
+/// One rule, such as "Status is Done" or "Budget is greater than 1,000".
+#[derive(Clone, PartialEq, Debug)]
+pub struct Condition {
+    pub scope: ColumnScope,
+    pub column: Id,
+    pub operator: Operator,
+    pub operand: Operand,
+}
+
+impl Condition {
+    pub fn new(scope: ColumnScope, column: &ColumnDef) -> Self {
+        Self {
+            scope,
+            column: column.id,
+            operator: Operator::choices(column.kind)[0],
+            operand: Operand::None,
+        }
+    }
+
+    /// The column the condition looks at, or `None` once it is deleted.
+    fn column<'a>(&self, board: &'a Board) -> Option<&'a ColumnDef> {
+        board
+            .columns_for(self.scope)
+            .iter()
+            .find(|column| column.id == self.column)
+    }
+
+    /// Whether the condition is applied: its column still exists and it has
+    /// a value to compare with.
+    fn applies(&self, board: &Board) -> bool {
+        let complete = match &self.operand {
+            _ if !self.operator.takes_operand() => true,
+            Operand::None => false,
+            Operand::Text(text) => !text.trim().is_empty(),
+            _ => true,
+        };
+        complete && self.column(board).is_some()
+    }
+
+    fn test(&self, board: &Board, value: &CellValue, today: NaiveDate) -> bool {
+        let hit = |hit: bool| hit.then_some(Ordering::Equal);
+        let person = |person: &Id| match &self.operand {
+            Operand::Person(id) => person == id,
+            Operand::Me => board.me == Some(*person),
+            _ => false,
+        };
+        // How the cell compares with the operand; `Equal` also stands for
+        // "has the label" or "contains the text".
+        let found = match (value, &self.operand) {
+            (CellValue::Money(money), Operand::Money(other)) => money.map(|money| money.cmp(other)),
+            (CellValue::Date(date), Operand::Date(other)) => {
+                date.map(|date| date.cmp(&other.resolve(today)))
+            }
+            // A span is before a day it ends before, and after one it starts
+            // after.
+            (CellValue::Timeline(timeline), Operand::Date(other)) => {
+                let day = other.resolve(today);
+                timeline.map(|timeline| {
+                    if timeline.end < day {
+                        Ordering::Less
+                    } else if timeline.start > day {
+                        Ordering::Greater
+                    } else {
+                        Ordering::Equal
+                    }
+                })
+            }
+            (CellValue::Status(status), Operand::Label(label)) => hit(*status == Some(*label)),
+            (CellValue::Select(option), Operand::Option(other)) => {
+                hit(option.as_ref() == Some(other))
+            }
+            (CellValue::Person(people), _) => hit(people.iter().any(person)),
+            (CellValue::Outreach(contacts), _) => {
+                hit(contacts.iter().any(|contact| person(&contact.person)))
+            }
+            (value, Operand::Text(text)) => {
+                hit(!find_all(&cell_text(board, value), text.trim()).is_empty())
+            }
+            _ => None,
+        };
+        match self.operator {
+            Operator::Is | Operator::Contains => found == Some(Ordering::Equal),
+            Operator::IsNot | Operator::DoesNotContain => found != Some(Ordering::Equal),
+            Operator::Above => found == Some(Ordering::Greater),
+            Operator::Below => found == Some(Ordering::Less),
+            Operator::AtLeast => matches!(found, Some(Ordering::Greater | Ordering::Equal)),
+            Operator::AtMost => matches!(found, Some(Ordering::Less | Ordering::Equal)),
+            Operator::IsEmpty => value.is_empty(),
+            Operator::IsNotEmpty => !value.is_empty(),
+        }
+    }
+}
+
+/// Conditions that all or any must hold.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct RuleGroup {
+    pub join: Join,
+    pub conditions: Vec<Condition>,
+}
+
+/// The filter builder's conditions: groups of conditions, of which all or
+/// any must hold. Conditions still missing a value, or on a column that has
+/// since been deleted, are left out.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Rules {
+    pub join: Join,
+    pub groups: Vec<RuleGroup>,
+}
+
+impl Rules {
+    fn applied<'a>(&'a self, board: &'a Board) -> impl Iterator<Item = &'a Condition> {
+        self.groups
+            .iter()
+            .flat_map(|group| group.conditions.iter())
+            .filter(move |condition| condition.applies(board))
+    }
+
+    /// How many conditions are applied.
+    pub fn count(&self, board: &Board) -> usize {
+        self.applied(board).count()
+    }
+
+    pub fn is_active(&self, board: &Board) -> bool {
+        self.count(board) > 0
+    }
+
+    /// Whether any applied condition looks at subitem columns.
+    pub fn uses_subitems(&self, board: &Board) -> bool {
+        self.applied(board)
+            .any(|condition| condition.scope == ColumnScope::Subitem)
+    }
+
+    /// Whether a task, or a task and one of its subitems, pass. Subitem
+    /// conditions fail when there is no subitem.
+    fn test(&self, board: &Board, task: &TaskData, subitem: Option<&SubitemData>) -> bool {
+        let today = today();
+        let test = |condition: &Condition| {
+            let cells = match condition.scope {
+                ColumnScope::Task => &task.cells,
+                ColumnScope::Subitem => match subitem {
+                    Some(subitem) => &subitem.cells,
+                    None => return false,
+                },
+            };
+            condition
+                .column(board)
+                .is_some_and(|column| condition.test(board, &column.cell(cells), today))
+        };
+        let groups = self.groups.iter().filter_map(|group| {
+            let mut conditions = group
+                .conditions
+                .iter()
+                .filter(|condition| condition.applies(board))
+                .peekable();
+            conditions.peek()?;
+            Some(group.join.combine(conditions.map(test)))
+        });
+        self.join.combine(groups)
+    }
+
+    /// Whether the task passes on its own or together with any subitem.
+    pub fn shows_task(&self, board: &Board, task: &TaskData) -> bool {
+        !self.is_active(board)
+            || self.test(board, task, None)
+            || task
+                .subitems
+                .iter()
+                .any(|subitem| self.test(board, task, Some(subitem)))
+    }
+
+    pub fn shows_subitem(&self, board: &Board, task: &TaskData, subitem: &SubitemData) -> bool {
+        self.test(board, task, Some(subitem))
+    }
+}
+
+/// Every column a condition can look at, task columns first.
+fn columns(board: &Board) -> Vec<(ColumnScope, &ColumnDef)> {
+    [ColumnScope::Task, ColumnScope::Subitem]
+        .into_iter()
+        .flat_map(|scope| {
+            board
+                .columns_for(scope)
+                .iter()
+                .map(move |column| (scope, column))
+        })
+        .collect()
+}
+
+fn selected(e: &Event) -> Option<usize> {
+    e.target_dyn_into::<HtmlSelectElement>()
+        .map(|select| select.selected_index() as usize)
+}
+
+#[derive(Properties, PartialEq)]
+pub struct FilterBuilderProps {
+    pub rules: Rules,
+    pub on_change: Callback<Rules>,
+}
+
+fn edit<E: 'static>(
+    props: &FilterBuilderProps,
+    change: impl Fn(&mut Rules, E) + 'static,
+) -> Callback<E> {
+    let on_change = props.on_change.clone();
+    let rules = props.rules.clone();
+    Callback::from(move |e: E| {
+        let mut rules = rules.clone();
+        change(&mut rules, e);
+        on_change.emit(rules);
+    })
+}
+
+/// The input for a condition's value, which depends on the column kind.
+fn operand_input(
+    board: &Board,
+    column: &ColumnDef,
+    operand: &Operand,
+    on_operand: Callback<Operand>,
+) -> Html {
+    let class = "p-1 border rounded";
+    let pick = |choices: Vec<(Operand, String)>, placeholder: &'static str| {
+        let options = choices.clone();
+        let on_change = on_operand.reform(move |e: Event| {
+            selected(&e)
+                .and_then(|index| index.checked_sub(1))
+                .and_then(|index| options.get(index))
+                .map_or(Operand::None, |(operand, _)| operand.clone())
+        });
+        html! {
+            <select class={class} onchange={on_change}>
+                <option selected={*operand == Operand::None}>{ placeholder }</option>
+                { for choices.iter().map(|(choice, label)| html! {
+                    <option selected={choice == operand}>{ label }</option>
+                }) }
+            </select>
+        }
+    };
+    match column.kind {
+        CellKind::Status => pick(
+            board
+                .status_labels
+                .iter()
+                .map(|label| (Operand::Label(label.id), label.name.clone()))
+                .collect(),
+            "Pick a label",
+        ),
+        CellKind::Select => pick(
+            column
+                .options
+                .iter()
+                .map(|option| (Operand::Option(option.clone()), option.clone()))
+                .collect(),
+            "Pick an option",
+        ),
+        CellKind::Person | CellKind::Outreach => pick(
+            std::iter::once((Operand::Me, "Me".to_string()))
+                .chain(
+                    board
+                        .people
+                        .iter()
+                        .map(|person| (Operand::Person(person.id), person.name.clone())),
+                )
+                .collect(),
+            "Pick someone",
+        ),
+        CellKind::Date | CellKind::Timeline => {
+            let mut presets: Vec<_> = DateOperand::PRESETS
+                .into_iter()
+                .map(|date| (Operand::Date(date), date.label()))
+                .collect();
+            let exact = match operand {
+                Operand::Date(DateOperand::On(date)) => Some(*date),
+                _ => None,
+            };
+            let on = Operand::Date(DateOperand::On(exact.unwrap_or_else(today)));
+            presets.push((on, "a date...".to_string()));
+            let on_date = on_operand.reform(|e: Event| {
+                e.target_dyn_into::<HtmlInputElement>()
+                    .and_then(|input| NaiveDate::parse_from_str(&input.value(), "%Y-%m-%d").ok())
+                    .map_or(Operand::None, |date| Operand::Date(DateOperand::On(date)))
+            });
+            html! {
+                <>
+                    { pick(presets, "Pick a date") }
+                    { if let Some(date) = exact {
+                        html! {
+                            <input class={class} type="date" value={date.format("%Y-%m-%d").to_string()} onchange={on_date} />
+                        }
+                    } else {
+                        html! {}
+                    }}
+                </>
+            }
+        }
+        CellKind::Money => {
+            let value = match operand {
+                Operand::Money(money) => money.to_string(),
+                _ => String::new(),
+            };
+            let on_change = on_operand.reform(|e: Event| {
+                e.target_dyn_into::<HtmlInputElement>()
+                    .and_then(|input| Money::parse(&input.value()).ok())
+                    .map_or(Operand::None, Operand::Money)
+            });
+            html! {
+                <input class={classes!(class, "w-28")} type="text" placeholder="Amount" value={value} onchange={on_change} />
+            }
+        }
+        CellKind::LongText | CellKind::Files => {
+            let value = match operand {
+                Operand::Text(text) => text.clone(),
+                _ => String::new(),
+            };
+            let on_input = on_operand.reform(|e: InputEvent| {
+                e.target_dyn_into::<HtmlInputElement>()
+                    .map_or(Operand::None, |input| Operand::Text(input.value()))
+            });
+            html! {
+                <input class={classes!(class, "w-40")} type="text" placeholder="Text" value={value} oninput={on_input} />
+            }
+        }
+    }
+}
+
+/// Builds [`Rules`]: groups of column conditions joined with AND or OR.
+/// Lists the groups that have no tasks left under the filter.
+#[function_component(FilterBuilder)]
+pub fn filter_builder(props: &FilterBuilderProps) -> Html {
+    let board = use_board();
+    let columns = columns(&board);
+    let join_select = |join: Join, on_change: Callback<Event>| {
+        html! {
+            <select class="p-1 border rounded" onchange={on_change}>
+                { for [Join::All, Join::Any].into_iter().map(|choice| html! {
+                    <option selected={choice == join}>{ choice.label() }</option>
+                }) }
+            </select>
+        }
+    };
+    let join_of = |index: Option<usize>| {
+        if index == Some(1) {
+            Join::Any
+        } else {
+            Join::All
+        }
+    };
+
+    let on_join = edit(props, move |rules, e: Event| {
+        rules.join = join_of(selected(&e))
+    });
+    let first_column = columns
+        .first()
+        .map(|(scope, column)| Condition::new(*scope, column));
+    let on_add_group = {
+        let first_column = first_column.clone();
+        edit(props, move |rules, _: MouseEvent| {
+            rules.groups.push(RuleGroup {
+                join: Join::All,
+                conditions: first_column.clone().into_iter().collect(),
+            });
+        })
+    };
+    let on_clear = edit(props, |rules, _: MouseEvent| *rules = Rules::default());
+
+    let empty: Vec<&str> = if props.rules.is_active(&board) {
+        board
+            .groups
+            .iter()
+            .filter(|group| {
+                !group
+                    .tasks
+                    .iter()
+                    .any(|task| props.rules.shows_task(&board, task))
+            })
+            .map(|group| group.name.as_str())
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    html! {
+        <div class="mt-4 p-4 border rounded-lg bg-gray-50">
+            <div class="flex items-center space-x-2 text-sm">
+                <span class="font-semibold">{"Filter"}</span>
+                <span>{"Show tasks matching"}</span>
+                { join_select(props.rules.join, on_join) }
+                <span>{"of these groups"}</span>
+                <span class="flex-1" />
+                <button class="text-red-600 disabled:text-gray-400" disabled={props.rules.groups.is_empty()} onclick={on_clear}>{"Clear"}</button>
+            </div>
+            { for props.rules.groups.iter().enumerate().map(|(index, group)| {
+                let on_group_join = edit(props, move |rules, e: Event| {
+                    rules.groups[index].join = join_of(selected(&e));
+                });
+                let on_add_condition = {
+                    let first_column = first_column.clone();
+                    edit(props, move |rules, _: MouseEvent| {
+                        rules.groups[index].conditions.extend(first_column.clone());
+                    })
+                };
+                let on_remove_group = edit(props, move |rules, _: MouseEvent| {
+                    rules.groups.remove(index);
+                });
+                html! {
+                    <div class="p-3 mt-3 space-y-2 text-sm bg-white border rounded-lg">
+                        <div class="flex items-center space-x-2">
+                            <span>{"Match"}</span>
+                            { join_select(group.join, on_group_join) }
+                            <span>{"of"}</span>
+                            <span class="flex-1" />
+                            <button class="text-red-600" onclick={on_remove_group}>{"Remove group"}</button>
+                        </div>
+                        { for group.conditions.iter().enumerate().map(|(position, condition)| {
+                            let on_remove = edit(props, move |rules, _: MouseEvent| {
+                                rules.groups[index].conditions.remove(position);
+                            });
+                            let Some(column) = condition.column(&board) else {
+                                return html! {
+                                    <div class="flex items-center space-x-2 text-gray-500">
+                                        <span>{"This condition's column was deleted, so it is left out."}</span>
+                                        <button class="px-1 text-gray-400 hover:text-red-600" onclick={on_remove} title="Remove condition">{"✕"}</button>
+                                    </div>
+                                };
+                            };
+                            let kind = column.kind;
+                            let on_column = {
+                                let conditions: Vec<_> = columns
+                                    .iter()
+                                    .map(|(scope, column)| Condition::new(*scope, column))
+                                    .collect();
+                                edit(props, move |rules, e: Event| {
+                                    if let Some(condition) = selected(&e).and_then(|index| conditions.get(index)) {
+                                        rules.groups[index].conditions[position] = condition.clone();
+                                    }
+                                })
+                            };
+                            let on_operator = edit(props, move |rules, e: Event| {
+                                if let Some(operator) = selected(&e).and_then(|index| Operator::choices(kind).get(index)) {
+                                    rules.groups[index].conditions[position].operator = *operator;
+                                }
+                            });
+                            let on_operand = edit(props, move |rules, operand: Operand| {
+                                rules.groups[index].conditions[position].operand = operand;
+                            });
+                            html! {
+                                <div class="flex items-center space-x-2">
+                                    <select class="p-1 border rounded" onchange={on_column}>
+                                        { for columns.iter().map(|(scope, each)| html! {
+                                            <option selected={*scope == condition.scope && each.id == column.id}>
+                                                { match scope {
+                                                    ColumnScope::Task => each.name.clone(),
+                                                    ColumnScope::Subitem => format!("Subitem: {}", each.name),
+                                                } }
+                                            </option>
+                                        }) }
+                                    </select>
+                                    <select class="p-1 border rounded" onchange={on_operator}>
+                                        { for Operator::choices(kind).iter().map(|operator| html! {
+                                            <option selected={*operator == condition.operator}>{ operator.label(kind) }</option>
+                                        }) }
+                                    </select>
+                                    { if condition.operator.takes_operand() {
+                                        operand_input(&board, column, &condition.operand, on_operand)
+                                    } else {
+                                        html! {}
+                                    }}
+                                    <button class="px-1 text-gray-400 hover:text-red-600" onclick={on_remove} title="Remove condition">{"✕"}</button>
+                                </div>
+                            }
+                        }) }
+                        <button class="text-blue-600" onclick={on_add_condition}>{"+ Condition"}</button>
+                    </div>
+                }
+            }) }
+            <button class="mt-3 text-sm text-blue-600" onclick={on_add_group}>{"+ Group of conditions"}</button>
+            { if empty.is_empty() {
+                html! {}
+            } else {
+                html! {
+                    <p class="mt-3 text-sm text-gray-500">
+                        { format!("Empty under this filter: {}", empty.join(", ")) }
+                    </p>
+                }
+            }}
+        </div>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cell::Timeline;
+    use crate::store::Cells;
+
+    fn day(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 3, day).unwrap()
+    }
+
+    fn label(board: &Board, name: &str) -> Id {
+        board
+            .status_labels
+            .iter()
+            .find(|label| label.name == name)
+            .unwrap()
+            .id
+    }
+
+    fn cells(values: Vec<(Id, CellValue)>) -> Cells {
+        values.into_iter().collect()
+    }
+
+    fn condition(column: Id, operator: Operator, operand: Operand) -> Condition {
+        Condition {
+            scope: ColumnScope::Task,
+            column,
+            operator,
+            operand,
+        }
+    }
+
+    fn rules(join: Join, groups: Vec<Vec<Condition>>) -> Rules {
+        Rules {
+            join,
+            groups: groups
+                .into_iter()
+                .map(|conditions| RuleGroup {
+                    join: Join::All,
+                    conditions,
+                })
+                .collect(),
+        }
+    }
+
+    /// A board with a budget and a timeline column, and a task that is Done,
+    /// costs 500.00, is dated the 12th and runs from the 10th to the 14th.
+    fn board() -> (Board, TaskData) {
+        let mut board = Board::default();
+        board
+            .columns
+            .push(ColumnDef::new(900, "Plan".to_string(), CellKind::Timeline));
+        let date = board.column_of_kind(ColumnScope::Task, CellKind::Date);
+        let status = board.column_of_kind(ColumnScope::Task, CellKind::Status);
+        let money = board.column_of_kind(ColumnScope::Task, CellKind::Money);
+        let done = label(&board, "Done");
+        let task = TaskData::new(
+            1000,
+            "Launch".to_string(),
+            cells(vec![
+                (status, CellValue::Status(Some(done))),
+                (money, CellValue::Money(Some(Money { cents: 50000 }))),
+                (date, CellValue::Date(Some(day(12)))),
+                (
+                    900,
+                    CellValue::Timeline(Some(Timeline::new(day(10), day(14)))),
+                ),
+            ]),
+        );
+        (board, task)
+    }
+
+    #[test]
+    fn compares_cells() {
+        let (board, task) = board();
+        let status = board.column_of_kind(ColumnScope::Task, CellKind::Status);
+        let money = board.column_of_kind(ColumnScope::Task, CellKind::Money);
+        let shows = |condition: Condition| {
+            rules(Join::All, vec![vec![condition]]).shows_task(&board, &task)
+        };
+        let done = Operand::Label(label(&board, "Done"));
+        let stuck = Operand::Label(label(&board, "Stuck"));
+        assert!(shows(condition(status, Operator::Is, done.clone())));
+        assert!(!shows(condition(status, Operator::IsNot, done)));
+        assert!(!shows(condition(status, Operator::Is, stuck)));
+        let amount = |cents| Operand::Money(Money { cents });
+        assert!(shows(condition(money, Operator::Above, amount(49999))));
+        assert!(!shows(condition(money, Operator::Above, amount(50000))));
+        assert!(shows(condition(money, Operator::AtLeast, amount(50000))));
+        assert!(shows(condition(money, Operator::IsNotEmpty, Operand::None)));
+    }
+
+    #[test]
+    fn compares_dates() {
+        let (board, task) = board();
+        let date = board.column_of_kind(ColumnScope::Task, CellKind::Date);
+        let shows = |operator: Operator, day: NaiveDate| {
+            let operand = Operand::Date(DateOperand::On(day));
+            rules(Join::All, vec![vec![condition(date, operator, operand)]])
+                .shows_task(&board, &task)
+        };
+        assert!(shows(Operator::Is, day(12)));
+        assert!(!shows(Operator::Is, day(13)));
+        assert!(shows(Operator::Below, day(13)));
+        assert!(!shows(Operator::Below, day(12)));
+        assert!(shows(Operator::Above, day(11)));
+    }
+
+    #[test]
+    fn spans_include_their_days() {
+        let (board, task) = board();
+        let shows = |operator: Operator, date: NaiveDate| {
+            let operand = Operand::Date(DateOperand::On(date));
+            rules(Join::All, vec![vec![condition(900, operator, operand)]])
+                .shows_task(&board, &task)
+        };
+        assert!(shows(Operator::Is, day(10)));
+        assert!(shows(Operator::Is, day(14)));
+        assert!(!shows(Operator::Is, day(15)));
+        assert!(shows(Operator::Below, day(15)));
+        assert!(shows(Operator::Above, day(9)));
+        assert!(!shows(Operator::Above, day(10)));
+    }
+
+    #[test]
+    fn groups_join() {
+        let (board, task) = board();
+        let status = board.column_of_kind(ColumnScope::Task, CellKind::Status);
+        let passes = condition(status, Operator::IsNotEmpty, Operand::None);
+        let fails = condition(status, Operator::IsEmpty, Operand::None);
+        let groups = vec![vec![passes.clone()], vec![fails.clone()]];
+        assert!(!rules(Join::All, groups.clone()).shows_task(&board, &task));
+        assert!(rules(Join::Any, groups).shows_task(&board, &task));
+        let mut any = rules(Join::All, vec![vec![passes, fails]]);
+        assert!(!any.shows_task(&board, &task));
+        any.groups[0].join = Join::Any;
+        assert!(any.shows_task(&board, &task));
+    }
+
+    #[test]
+    fn unfinished_and_orphaned_conditions_are_left_out() {
+        let (board, task) = board();
+        let status = board.column_of_kind(ColumnScope::Task, CellKind::Status);
+        let notes = board.column_of_kind(ColumnScope::Task, CellKind::LongText);
+        let rules = rules(
+            Join::All,
+            vec![vec![
+                condition(status, Operator::Is, Operand::None),
+                condition(notes, Operator::Contains, Operand::Text("  ".to_string())),
+                condition(4242, Operator::IsNotEmpty, Operand::None),
+            ]],
+        );
+        assert_eq!(rules.count(&board), 0);
+        assert!(!rules.is_active(&board));
+        assert!(rules.shows_task(&board, &task));
+    }
+
+    #[test]
+    fn subitem_conditions_need_a_subitem() {
+        let (board, mut task) = board();
+        let status = board.column_of_kind(ColumnScope::Subitem, CellKind::Status);
+        let stuck = label(&board, "Stuck");
+        let rules = rules(
+            Join::All,
+            vec![vec![Condition {
+                scope: ColumnScope::Subitem,
+                column: status,
+                operator: Operator::Is,
+                operand: Operand::Label(stuck),
+            }]],
+        );
+        assert!(rules.uses_subitems(&board));
+        assert!(!rules.shows_task(&board, &task));
+        task.subitems
+            .push(SubitemData::new(1001, "Fine".to_string(), Cells::new()));
+        task.subitems.push(SubitemData::new(
+            1002,
+            "Blocked".to_string(),
+            cells(vec![(status, CellValue::Status(Some(stuck)))]),
+        ));
+        assert!(rules.shows_task(&board, &task));
+        assert!(!rules.shows_subitem(&board, &task, &task.subitems[0]));
+        assert!(rules.shows_subitem(&board, &task, &task.subitems[1]));
+    }
+}
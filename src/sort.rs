@@ -0,0 +1,278 @@
+use crate::cell::CellValue;
+use crate::column::ColumnDef;
+use crate::markdown::plain_text;
+use crate::outreach::Progress;
+use crate::store::{Board, Id, TaskData};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use yew::prelude::*;
+
+/// What tasks can be sorted by.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum SortColumn {
+    Name,
+    Column(Id),
+}
+
+/// One level of a sort. A list of these sorts by the first, then breaks
+/// ties with the next.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct SortKey {
+    pub column: SortColumn,
+    pub descending: bool,
+}
+
+/// Compares text ignoring case.
+fn compare_text(a: &str, b: &str) -> Ordering {
+    a.to_lowercase().cmp(&b.to_lowercase())
+}
+
+/// Compares two non-empty cells of `column` the way their kind reads:
+/// labels and options in the order they are listed, people by name, notes by
+/// their text, files by how many there are and outreach by progress.
+fn compare_cells(board: &Board, column: &ColumnDef, a: &CellValue, b: &CellValue) -> Ordering {
+    let label = |status: &Option<Id>| {
+        board
+            .status_labels
+            .iter()
+            .position(|label| Some(label.id) == *status)
+    };
+    let option = |option: &Option<String>| {
+        column
+            .options
+            .iter()
+            .position(|each| Some(each) == option.as_ref())
+    };
+    let first_name = |people: &[Id]| {
+        people
+            .first()
+            .and_then(|person| board.person(*person))
+            .map(|person| person.name.clone())
+            .unwrap_or_default()
+    };
+    match (a, b) {
+        (CellValue::Status(a), CellValue::Status(b)) => label(a).cmp(&label(b)),
+        (CellValue::Date(a), CellValue::Date(b)) => a.cmp(b),
+        (CellValue::Timeline(a), CellValue::Timeline(b)) => a.cmp(b),
+        (CellValue::Select(a), CellValue::Select(b)) => {
+            option(a).cmp(&option(b)).then_with(|| a.cmp(b))
+        }
+        (CellValue::Person(a), CellValue::Person(b)) => {
+            compare_text(&first_name(a), &first_name(b)).then(a.len().cmp(&b.len()))
+        }
+        (CellValue::LongText(a), CellValue::LongText(b)) => {
+            compare_text(&plain_text(a), &plain_text(b))
+        }
+        (CellValue::Files(a), CellValue::Files(b)) => a.len().cmp(&b.len()),
+        (CellValue::Money(a), CellValue::Money(b)) => a.cmp(b),
+        (CellValue::Outreach(a), CellValue::Outreach(b)) => {
+            let (a, b) = (Progress::of(a), Progress::of(b));
+            // Compare responded / total without dividing.
+            (a.responded * b.total)
+                .cmp(&(b.responded * a.total))
+                .then(a.total.cmp(&b.total))
+        }
+        _ => Ordering::Equal,
+    }
+}
+
+fn compare_tasks(board: &Board, key: &SortKey, a: &TaskData, b: &TaskData) -> Ordering {
+    let ordering = match key.column {
+        SortColumn::Name => compare_text(&a.name, &b.name),
+        SortColumn::Column(id) => {
+            let Some(column) = board.columns.iter().find(|column| column.id == id) else {
+                return Ordering::Equal;
+            };
+            let (a, b) = (column.cell(&a.cells), column.cell(&b.cells));
+            // Empty cells go last whichever way the column is sorted.
+            match (a.is_empty(), b.is_empty()) {
+                (true, true) => return Ordering::Equal,
+                (true, false) => return Ordering::Greater,
+                (false, true) => return Ordering::Less,
+                (false, false) => compare_cells(board, column, &a, &b),
+            }
+        }
+    };
+    if key.descending {
+        ordering.reverse()
+    } else {
+        ordering
+    }
+}
+
+/// Sorts tasks by each key in turn. Tasks that tie on every key keep their
+/// order on the board.
+pub fn sort_tasks(board: &Board, tasks: &mut [&TaskData], keys: &[SortKey]) {
+    tasks.sort_by(|a, b| {
+        keys.iter()
+            .map(|key| compare_tasks(board, key, a, b))
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or(Ordering::Equal)
+    });
+}
+
+/// What clicking a header does to the sort: sort by that column alone, or
+/// with `add` (shift-click) add it as the next level. Clicking a sorted
+/// column again flips it to descending, and a third time drops it.
+pub fn toggle(keys: &[SortKey], column: SortColumn, add: bool) -> Vec<SortKey> {
+    let current = keys.iter().find(|key| key.column == column).copied();
+    let next = match current {
+        None => Some(SortKey {
+            column,
+            descending: false,
+        }),
+        Some(SortKey {
+            descending: false, ..
+        }) => Some(SortKey {
+            column,
+            descending: true,
+        }),
+        Some(_) => None,
+    };
+    if !add {
+        return next.into_iter().collect();
+    }
+    let mut keys = keys.to_vec();
+    match (keys.iter().position(|key| key.column == column), next) {
+        (Some(index), Some(next)) => keys[index] = next,
+        (Some(index), None) => {
+            keys.remove(index);
+        }
+        (None, Some(next)) => keys.push(next),
+        (None, None) => {}
+    }
+    keys
+}
+
+#[derive(Properties, PartialEq)]
+pub struct SortHeaderProps {
+    pub label: String,
+    pub column: SortColumn,
+    pub keys: Vec<SortKey>,
+    pub on_sort: Callback<Vec<SortKey>>,
+}
+
+/// A column header that sorts by its column when clicked. Shift-click to
+/// sort by several columns. Shows the direction, and the level when there
+/// is more than one.
+#[function_component(SortHeader)]
+pub fn sort_header(props: &SortHeaderProps) -> Html {
+    let on_click = {
+        let keys = props.keys.clone();
+        let column = props.column;
+        let on_sort = props.on_sort.clone();
+        Callback::from(move |e: MouseEvent| on_sort.emit(toggle(&keys, column, e.shift_key())))
+    };
+    let position = props.keys.iter().position(|key| key.column == props.column);
+    let indicator = position.map(|index| {
+        let arrow = if props.keys[index].descending {
+            "▼"
+        } else {
+            "▲"
+        };
+        if props.keys.len() > 1 {
+            format!("{}{}", arrow, index + 1)
+        } else {
+            arrow.to_string()
+        }
+    });
+
+    html! {
+        <button
+            class="flex items-center space-x-1 text-left hover:text-blue-600"
+            onclick={on_click}
+            title="Sort by this column. Shift-click to add it to the sort."
+        >
+            <span class="truncate">{ &props.label }</span>
+            { if let Some(indicator) = indicator {
+                html! { <span class="text-xs text-blue-600">{ indicator }</span> }
+            } else {
+                html! {}
+            }}
+        </button>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cell::{CellKind, Money};
+    use crate::column::ColumnScope;
+    use crate::store::Cells;
+
+    fn key(column: SortColumn, descending: bool) -> SortKey {
+        SortKey { column, descending }
+    }
+
+    #[test]
+    fn toggle_cycles_through_ascending_descending_and_off() {
+        let name = SortColumn::Name;
+        let keys = toggle(&[], name, false);
+        assert_eq!(keys, vec![key(name, false)]);
+        let keys = toggle(&keys, name, false);
+        assert_eq!(keys, vec![key(name, true)]);
+        assert_eq!(toggle(&keys, name, false), vec![]);
+    }
+
+    #[test]
+    fn toggle_adds_levels_with_shift() {
+        let (name, budget) = (SortColumn::Name, SortColumn::Column(7));
+        let keys = vec![key(name, false)];
+        assert_eq!(toggle(&keys, budget, false), vec![key(budget, false)]);
+        let keys = toggle(&keys, budget, true);
+        assert_eq!(keys, vec![key(name, false), key(budget, false)]);
+        let keys = toggle(&keys, name, true);
+        assert_eq!(keys, vec![key(name, true), key(budget, false)]);
+        assert_eq!(toggle(&keys, name, true), vec![key(budget, false)]);
+    }
+
+    fn task(id: Id, name: &str, cells: Vec<(Id, CellValue)>) -> TaskData {
+        TaskData::new(id, name.to_string(), cells.into_iter().collect::<Cells>())
+    }
+
+    fn names(tasks: &[&TaskData]) -> Vec<String> {
+        tasks.iter().map(|task| task.name.clone()).collect()
+    }
+
+    #[test]
+    fn sorts_by_label_order_with_empty_cells_last() {
+        let board = Board::default();
+        let status = board.column_of_kind(ColumnScope::Task, CellKind::Status);
+        let label = |index: usize| CellValue::Status(Some(board.status_labels[index].id));
+        let tasks = [
+            task(1, "Done", vec![(status, label(2))]),
+            task(2, "Unset", vec![]),
+            task(3, "Working", vec![(status, label(0))]),
+            task(4, "Stuck", vec![(status, label(1))]),
+        ];
+        let mut sorted: Vec<&TaskData> = tasks.iter().collect();
+        let by = SortColumn::Column(status);
+        sort_tasks(&board, &mut sorted, &[key(by, false)]);
+        assert_eq!(names(&sorted), ["Working", "Stuck", "Done", "Unset"]);
+        sort_tasks(&board, &mut sorted, &[key(by, true)]);
+        assert_eq!(names(&sorted), ["Done", "Stuck", "Working", "Unset"]);
+    }
+
+    #[test]
+    fn later_keys_break_ties_and_full_ties_keep_their_order() {
+        let board = Board::default();
+        let money = board.column_of_kind(ColumnScope::Task, CellKind::Money);
+        let budget = |cents| CellValue::Money(Some(Money { cents }));
+        let tasks = [
+            task(1, "beta", vec![(money, budget(100))]),
+            task(2, "Alpha", vec![(money, budget(100))]),
+            task(3, "gamma", vec![(money, budget(-50))]),
+            task(4, "alpha", vec![(money, budget(100))]),
+        ];
+        let mut sorted: Vec<&TaskData> = tasks.iter().collect();
+        sort_tasks(&board, &mut sorted, &[key(SortColumn::Name, false)]);
+        assert_eq!(names(&sorted), ["Alpha", "alpha", "beta", "gamma"]);
+        let keys = [
+            key(SortColumn::Column(money), true),
+            key(SortColumn::Name, true),
+        ];
+        let mut sorted: Vec<&TaskData> = tasks.iter().collect();
+        sort_tasks(&board, &mut sorted, &keys);
+        assert_eq!(names(&sorted), ["beta", "Alpha", "alpha", "gamma"]);
+    }
+}
@@ -0,0 +1,239 @@
+use crate::cell::CellValue;
+use crate::store::{use_board, BoardAction, Id};
+use serde::{Deserialize, Serialize};
+use web_sys::{HtmlInputElement, HtmlSelectElement, KeyboardEvent};
+use yew::prelude::*;
+
+/// Colors a status label can take. Each is a Tailwind color name.
+pub const LABEL_COLORS: [&str; 8] = [
+    "gray", "orange", "red", "green", "blue", "purple", "yellow", "pink",
+];
+
+/// A label color for the `index`th value, skipping gray.
+pub fn cycle_color(index: usize) -> String {
+    LABEL_COLORS[1 + index % (LABEL_COLORS.len() - 1)].to_string()
+}
+
+/// One of the board's status choices, e.g. "Working on it" in orange.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct StatusLabel {
+    pub id: Id,
+    pub name: String,
+    pub color: String,
+}
+
+pub fn default_status_labels(mut next_id: impl FnMut() -> Id) -> Vec<StatusLabel> {
+    [
+        ("Working on it", "orange"),
+        ("Stuck", "red"),
+        ("Done", "green"),
+    ]
+    .into_iter()
+    .map(|(name, color)| StatusLabel {
+        id: next_id(),
+        name: name.to_string(),
+        color: color.to_string(),
+    })
+    .collect()
+}
+
+#[derive(Properties, PartialEq)]
+pub struct StatusPickerProps {
+    pub value: Option<Id>,
+    pub on_commit: Callback<CellValue>,
+}
+
+/// A status cell: a colored pill that opens a popover of the board's labels.
+#[function_component(StatusPicker)]
+pub fn status_picker(props: &StatusPickerProps) -> Html {
+    let board = use_board();
+    let is_open = use_state(|| false);
+
+    let current = props
+        .value
+        .and_then(|id| board.status_labels.iter().find(|label| label.id == id));
+
+    let toggle = {
+        let is_open = is_open.clone();
+        Callback::from(move |_| {
+            is_open.set(!*is_open);
+        })
+    };
+
+    let close = {
+        let is_open = is_open.clone();
+        Callback::from(move |_| {
+            is_open.set(false);
+        })
+    };
+
+    let pick = |value: Option<Id>| {
+        let is_open = is_open.clone();
+        let on_commit = props.on_commit.clone();
+        Callback::from(move |_| {
+            is_open.set(false);
+            on_commit.emit(CellValue::Status(value));
+        })
+    };
+
+    html! {
+        <div class="relative" onmouseleave={close}>
+            <button
+                class={match current {
+                    Some(label) => format!("w-full rounded px-2 py-1 text-sm text-white bg-{}-500", label.color),
+                    None => "w-full rounded px-2 py-1 text-sm text-gray-500 bg-gray-100".to_string(),
+                }}
+                onclick={toggle}
+            >
+                { current.map(|label| label.name.as_str()).unwrap_or("-") }
+            </button>
+            { if *is_open {
+                html! {
+                    <ul class="absolute z-10 mt-1 w-40 p-1 bg-white border rounded-lg shadow-lg space-y-1">
+                        { for board.status_labels.iter().map(|label| html! {
+                            <li
+                                key={label.id}
+                                class={format!("rounded px-2 py-1 text-sm text-white cursor-pointer bg-{}-500", label.color)}
+                                onclick={pick(Some(label.id))}
+                            >
+                                { &label.name }
+                            </li>
+                        }) }
+                        <li class="rounded px-2 py-1 text-sm text-gray-500 cursor-pointer hover:bg-gray-100" onclick={pick(None)}>
+                            {"Clear"}
+                        </li>
+                    </ul>
+                }
+            } else {
+                html! {}
+            }}
+        </div>
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct StatusBarProps {
+    /// The status of each item being summarised.
+    pub values: Vec<Option<Id>>,
+}
+
+/// A thin bar split by how many items carry each status label.
+#[function_component(StatusBar)]
+pub fn status_bar(props: &StatusBarProps) -> Html {
+    let board = use_board();
+    let total = props.values.len();
+    if total == 0 {
+        return html! {};
+    }
+
+    let mut segments: Vec<(String, String, usize)> = board
+        .status_labels
+        .iter()
+        .map(|label| {
+            let count = props
+                .values
+                .iter()
+                .filter(|value| **value == Some(label.id))
+                .count();
+            (label.name.clone(), format!("bg-{}-500", label.color), count)
+        })
+        .collect();
+    let labelled: usize = segments.iter().map(|(_, _, count)| count).sum();
+    segments.push((
+        "No status".to_string(),
+        "bg-gray-200".to_string(),
+        total - labelled,
+    ));
+
+    html! {
+        <div class="flex w-32 h-3 mr-3 overflow-hidden rounded">
+            { for segments.into_iter().filter(|(_, _, count)| *count > 0).map(|(name, color, count)| html! {
+                <div
+                    class={classes!("h-full", color)}
+                    style={format!("width: {}%;", count as f64 * 100.0 / total as f64)}
+                    title={format!("{}: {}", name, count)}
+                />
+            }) }
+        </div>
+    }
+}
+
+/// Lets the user add, rename, recolor and remove the board's status labels.
+#[function_component(StatusLabelsPanel)]
+pub fn status_labels_panel() -> Html {
+    let board = use_board();
+    let new_name = use_state(String::new);
+
+    let on_new_name = {
+        let new_name = new_name.clone();
+        Callback::from(move |e: InputEvent| {
+            if let Some(input) = e.target_dyn_into::<HtmlInputElement>() {
+                new_name.set(input.value());
+            }
+        })
+    };
+
+    let on_add = {
+        let board = board.clone();
+        let new_name = new_name.clone();
+        Callback::from(move |_| {
+            let name = new_name.trim();
+            if !name.is_empty() {
+                board.dispatch(BoardAction::AddStatusLabel {
+                    name: name.to_string(),
+                    color: LABEL_COLORS[board.status_labels.len() % LABEL_COLORS.len()].to_string(),
+                });
+                new_name.set(String::new());
+            }
+        })
+    };
+
+    html! {
+        <div class="mt-4 p-4 border rounded-lg bg-gray-50">
+            <span class="font-semibold">{"Status labels"}</span>
+            <ul class="mt-2 space-y-2">
+                { for board.status_labels.iter().map(|label| {
+                    let id = label.id;
+                    let on_rename = {
+                        let board = board.clone();
+                        Callback::from(move |e: KeyboardEvent| {
+                            if e.key() == "Enter" {
+                                if let Some(input) = e.target_dyn_into::<HtmlInputElement>() {
+                                    board.dispatch(BoardAction::RenameStatusLabel { label: id, name: input.value() });
+                                }
+                            }
+                        })
+                    };
+                    let on_color = {
+                        let board = board.clone();
+                        Callback::from(move |e: Event| {
+                            if let Some(select) = e.target_dyn_into::<HtmlSelectElement>() {
+                                board.dispatch(BoardAction::SetStatusLabelColor { label: id, color: select.value() });
+                            }
+                        })
+                    };
+                    let on_remove = {
+                        let board = board.clone();
+                        Callback::from(move |_| board.dispatch(BoardAction::RemoveStatusLabel { label: id }))
+                    };
+                    html! {
+                        <li key={id} class="flex items-center space-x-2 text-sm">
+                            <span class={format!("w-4 h-4 rounded bg-{}-500", label.color)} />
+                            <input class="border rounded p-1 w-48" type="text" value={label.name.clone()} onkeydown={on_rename} />
+                            <select class="border rounded p-1" onchange={on_color}>
+                                { for LABEL_COLORS.into_iter().map(|color| html! {
+                                    <option value={color} selected={label.color == color}>{ color }</option>
+                                }) }
+                            </select>
+                            <button class="text-red-600" onclick={on_remove}>{"Remove"}</button>
+                        </li>
+                    }
+                }) }
+            </ul>
+            <div class="flex items-center mt-3 space-x-2 text-sm">
+                <input class="border rounded p-1 w-48" type="text" placeholder="New label" value={(*new_name).clone()} oninput={on_new_name} />
+                <button class="p-1 px-2 bg-blue-500 text-white rounded" onclick={on_add}>{"Add label"}</button>
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,980 @@
+use crate::cell::{CellKind, CellValue};
+use crate::people::Person;
+use crate::status::default_status_labels;
+use crate::store::Board;
+use gloo_console::log;
+use gloo_storage::{LocalStorage, Storage};
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+
+const STORAGE_KEY: &str = "sample-monday.board";
+
+/// Bump this whenever [`Board`] changes in a way `#[serde(default)]` can't
+/// absorb, and teach [`migrate`] how to bring the previous version forward.
+const SCHEMA_VERSION: u64 = 8;
+
+#[derive(Serialize)]
+struct SavedBoard<'a> {
+    version: u64,
+    board: &'a Board,
+}
+
+/// Restores the board saved by [`save`], falling back to the sample board
+/// when nothing is stored or the saved data cannot be read.
+pub fn load() -> Board {
+    let Ok(saved) = LocalStorage::get::<Value>(STORAGE_KEY) else {
+        return Board::default();
+    };
+    let version = saved.get("version").and_then(Value::as_u64).unwrap_or(0);
+    let Some(board) = saved.get("board").cloned() else {
+        log!("Saved board has no data, starting fresh");
+        return Board::default();
+    };
+    match migrate(version, board)
+        .and_then(|board| serde_json::from_value(board).map_err(|err| err.to_string()))
+    {
+        Ok(board) => board,
+        Err(err) => {
+            log!(format!("Could not restore saved board: {}", err));
+            Board::default()
+        }
+    }
+}
+
+pub fn save(board: &Board) {
+    let saved = SavedBoard {
+        version: SCHEMA_VERSION,
+        board,
+    };
+    if let Err(err) = LocalStorage::set(STORAGE_KEY, saved) {
+        log!(format!("Could not save board: {}", err));
+    }
+}
+
+/// Upgrades raw saved JSON one version at a time until it matches
+/// [`SCHEMA_VERSION`].
+fn migrate(mut version: u64, mut board: Value) -> Result<Value, String> {
+    while version < SCHEMA_VERSION {
+        board = match version {
+            1 => assign_ids(board),
+            2 => type_cells(board),
+            3 => add_columns(board),
+            4 => split_subitem_columns(board)?,
+            5 => add_status_labels(board),
+            6 => link_people(board),
+            7 => track_outreach(board),
+            _ => return Err(format!("unsupported schema version {}", version)),
+        };
+        version += 1;
+    }
+    if version == SCHEMA_VERSION {
+        Ok(board)
+    } else {
+        Err(format!("unsupported schema version {}", version))
+    }
+}
+
+/// v1 -> v2: groups, tasks and subitems gain a stable `id` and the board
+/// tracks the next free one.
+fn assign_ids(mut board: Value) -> Value {
+    let mut next_id = 1;
+    let mut assign = |item: &mut Value| {
+        if let Some(item) = item.as_object_mut() {
+            item.insert("id".to_string(), next_id.into());
+            next_id += 1;
+        }
+    };
+    for group in array_mut(&mut board, "groups") {
+        assign(group);
+        for task in array_mut(group, "tasks") {
+            assign(task);
+            for subitem in array_mut(task, "subitems") {
+                assign(subitem);
+            }
+        }
+    }
+    if let Some(board) = board.as_object_mut() {
+        board.insert("next_id".to_string(), next_id.into());
+    }
+    board
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Level {
+    Task,
+    Subitem,
+}
+
+/// Calls `visit` on every saved task and subitem, including those sitting in
+/// the trash.
+fn for_each_item(board: &mut Value, visit: &mut dyn FnMut(&mut Value, Level)) {
+    fn visit_task(task: &mut Value, visit: &mut dyn FnMut(&mut Value, Level)) {
+        visit(task, Level::Task);
+        for subitem in array_mut(task, "subitems") {
+            visit(subitem, Level::Subitem);
+        }
+    }
+    fn visit_group(group: &mut Value, visit: &mut dyn FnMut(&mut Value, Level)) {
+        for task in array_mut(group, "tasks") {
+            visit_task(task, visit);
+        }
+    }
+
+    for group in array_mut(board, "groups") {
+        visit_group(group, visit);
+    }
+    for entry in array_mut(board, "trash") {
+        if let Some(group) = entry.pointer_mut("/Group/group") {
+            visit_group(group, visit);
+        } else if let Some(task) = entry.pointer_mut("/Task/task") {
+            visit_task(task, visit);
+        } else if let Some(subitem) = entry.pointer_mut("/Subitem/subitem") {
+            visit(subitem, Level::Subitem);
+        }
+    }
+}
+
+fn array_mut<'a>(value: &'a mut Value, key: &str) -> impl Iterator<Item = &'a mut Value> {
+    value
+        .get_mut(key)
+        .and_then(Value::as_array_mut)
+        .into_iter()
+        .flatten()
+}
+
+/// v2 -> v3: the free-text date/area/owner/notes/files/budget strings become
+/// typed cells. Text that doesn't parse leaves the cell empty.
+fn type_cells(mut board: Value) -> Value {
+    for_each_item(&mut board, &mut |item, level| match level {
+        Level::Task => type_item_cells(item, "owner"),
+        Level::Subitem => type_item_cells(item, "people"),
+    });
+    board
+}
+
+/// The fixed cells of v3, in column order: their key in the v3 `cells` map
+/// and their kind.
+const V3_FIELDS: [(&str, CellKind); 6] = [
+    ("Date", CellKind::Date),
+    ("Area", CellKind::Select),
+    ("Owner", CellKind::Person),
+    ("Notes", CellKind::LongText),
+    ("Files", CellKind::Files),
+    ("Budget", CellKind::Money),
+];
+
+fn type_item_cells(item: &mut Value, owner_key: &str) {
+    let Some(item) = item.as_object_mut() else {
+        return;
+    };
+    let mut cells = Map::new();
+    for (field, kind) in V3_FIELDS {
+        let key = match field {
+            "Owner" => owner_key.to_string(),
+            field => field.to_lowercase(),
+        };
+        let text = take_string(item, &key);
+        // Files used to hold a bare count, which has no names to keep.
+        let text = if kind == CellKind::Files && text.chars().all(|c| c.is_ascii_digit()) {
+            String::new()
+        } else {
+            text
+        };
+        // Until v7, people cells held names rather than directory ids.
+        let value = if kind == CellKind::Person {
+            let names: Vec<&str> = text
+                .split(',')
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+                .collect();
+            Ok(serde_json::json!({ "Person": names }))
+        } else {
+            let value = CellValue::parse(kind, &text).unwrap_or_else(|_| CellValue::empty(kind));
+            serde_json::to_value(value)
+        };
+        if let Ok(value) = value {
+            cells.insert(field.to_string(), value);
+        }
+    }
+    item.insert("cells".to_string(), Value::Object(cells));
+}
+
+fn take_string(item: &mut Map<String, Value>, key: &str) -> String {
+    match item.remove(key) {
+        Some(Value::String(text)) => text,
+        _ => String::new(),
+    }
+}
+
+/// v3 -> v4: the fixed cells become the board's first configurable columns,
+/// and cells are keyed by column id instead of by name.
+fn add_columns(mut board: Value) -> Value {
+    let mut next_id = board.get("next_id").and_then(Value::as_u64).unwrap_or(1);
+    // The columns as v4 saved them, written out rather than taken from
+    // `standard_columns` so that later changes to it don't change this step.
+    let columns: Vec<Value> = [
+        ("Date", "Date", json!({ "Date": "2024-08-11" })),
+        ("Area", "Select", json!({ "Select": "Area 1" })),
+        ("Project Owner", "Person", json!({ "Person": ["Owner 1"] })),
+        ("Notes", "LongText", json!({ "LongText": "Sample Note" })),
+        ("Files / Image Capture", "Files", json!({ "Files": [] })),
+        ("Budget/Price", "Money", json!({ "Money": { "cents": 0 } })),
+    ]
+    .into_iter()
+    .map(|(name, kind, default)| {
+        next_id += 1;
+        let mut column = json!({
+            "id": next_id - 1,
+            "name": name,
+            "kind": kind,
+            "width": 160,
+            "default": default,
+        });
+        if kind == "Select" {
+            column["options"] = json!(["Area 1", "Area 2", "Area 3"]);
+        }
+        column
+    })
+    .collect();
+    let keys: Vec<(&str, String)> = V3_FIELDS
+        .iter()
+        .zip(&columns)
+        .map(|((field, _), column)| (*field, column["id"].to_string()))
+        .collect();
+    let rekey = |item: &mut Value| {
+        if let Some(Value::Object(cells)) = item.get_mut("cells") {
+            *cells = std::mem::take(cells)
+                .into_iter()
+                .filter_map(|(key, value)| {
+                    keys.iter()
+                        .find(|(field, _)| *field == key)
+                        .map(|(_, id)| (id.clone(), value))
+                })
+                .collect();
+        }
+    };
+    for_each_item(&mut board, &mut |item, _| rekey(item));
+    if let Some(board) = board.as_object_mut() {
+        board.insert("columns".to_string(), Value::Array(columns));
+        board.insert("next_id".to_string(), next_id.into());
+    }
+    board
+}
+
+/// v4 -> v5: subitems get their own column list, starting as a copy of the
+/// task columns under fresh ids.
+fn split_subitem_columns(mut board: Value) -> Result<Value, String> {
+    let mut next_id = board.get("next_id").and_then(Value::as_u64).unwrap_or(1);
+    let columns = board
+        .get("columns")
+        .and_then(Value::as_array)
+        .ok_or("saved board has no columns")?;
+    let mut ids = Vec::new();
+    let mut subitem_columns = Vec::new();
+    for column in columns {
+        let id = column
+            .get("id")
+            .and_then(Value::as_u64)
+            .ok_or("saved column has no id")?;
+        let mut column = column.clone();
+        if let Some(column) = column.as_object_mut() {
+            column.insert("id".to_string(), next_id.into());
+        }
+        ids.push((id.to_string(), next_id.to_string()));
+        subitem_columns.push(column);
+        next_id += 1;
+    }
+    for_each_item(&mut board, &mut |item, level| {
+        if level != Level::Subitem {
+            return;
+        }
+        if let Some(Value::Object(cells)) = item.get_mut("cells") {
+            *cells = std::mem::take(cells)
+                .into_iter()
+                .filter_map(|(key, value)| {
+                    ids.iter()
+                        .find(|(old, _)| *old == key)
+                        .map(|(_, new)| (new.clone(), value))
+                })
+                .collect();
+        }
+    });
+    if let Some(board) = board.as_object_mut() {
+        board.insert("subitem_columns".to_string(), Value::Array(subitem_columns));
+        board.insert("next_id".to_string(), next_id.into());
+    }
+    Ok(board)
+}
+
+/// v5 -> v6: the board gains its set of status labels, starting from the
+/// defaults, and a status column on tasks and subitems in the place new
+/// boards have it.
+fn add_status_labels(mut board: Value) -> Value {
+    let mut next_id = board.get("next_id").and_then(Value::as_u64).unwrap_or(1);
+    for key in ["columns", "subitem_columns"] {
+        if let Some(Value::Array(columns)) = board.get_mut(key) {
+            let column = json!({
+                "id": next_id,
+                "name": "Status",
+                "kind": "Status",
+                "width": 160,
+                "default": { "Status": null },
+            });
+            columns.insert(3.min(columns.len()), column);
+            next_id += 1;
+        }
+    }
+    let labels = default_status_labels(|| {
+        next_id += 1;
+        next_id - 1
+    });
+    if let Some(board) = board.as_object_mut() {
+        board.insert(
+            "status_labels".to_string(),
+            serde_json::to_value(&labels).unwrap_or_default(),
+        );
+        board.insert("next_id".to_string(), next_id.into());
+    }
+    board
+}
+
+/// v6 -> v7: the names typed into people cells become entries in the board's
+/// people directory, and cells list them by id.
+fn link_people(mut board: Value) -> Value {
+    let mut next_id = board.get("next_id").and_then(Value::as_u64).unwrap_or(1);
+    let mut people: Vec<Person> = Vec::new();
+    let mut link = |value: &mut Value| {
+        let Some(Value::Array(names)) = value.get_mut("Person") else {
+            return;
+        };
+        let ids: Vec<Value> = names
+            .iter()
+            .filter_map(Value::as_str)
+            .map(|name| {
+                let id = match people.iter().find(|person| person.name == name) {
+                    Some(person) => person.id,
+                    None => {
+                        let index = people.len();
+                        people.push(Person::new(next_id, name.to_string(), String::new(), index));
+                        next_id += 1;
+                        next_id - 1
+                    }
+                };
+                id.into()
+            })
+            .collect();
+        *names = ids;
+    };
+    for key in ["columns", "subitem_columns"] {
+        for column in array_mut(&mut board, key) {
+            if let Some(default) = column.get_mut("default") {
+                link(default);
+            }
+        }
+    }
+    for_each_item(&mut board, &mut |item, _| {
+        if let Some(Value::Object(cells)) = item.get_mut("cells") {
+            cells.values_mut().for_each(&mut link);
+        }
+    });
+    if let Some(board) = board.as_object_mut() {
+        board.insert(
+            "people".to_string(),
+            serde_json::to_value(&people).unwrap_or_default(),
+        );
+        board.insert("next_id".to_string(), next_id.into());
+    }
+    board
+}
+
+/// v7 -> v8: the subitem "People - Sent/Responded" column becomes an outreach
+/// column. Everyone it listed is kept, not yet contacted. Boards whose
+/// subitem columns were copied from the task columns, or that renamed it,
+/// have no column by that name; their first people column is converted.
+fn track_outreach(mut board: Value) -> Value {
+    let to_outreach = |value: &mut Value| {
+        if let Some(Value::Array(people)) = value.get("Person") {
+            let contacts: Vec<Value> = people
+                .iter()
+                .filter_map(Value::as_u64)
+                .map(|person| json!({ "person": person, "sent": null, "responded": null }))
+                .collect();
+            *value = json!({ "Outreach": contacts });
+        }
+    };
+    let is_people = |column: &Value| column.get("kind").and_then(Value::as_str) == Some("Person");
+    let columns: Vec<&Value> = board
+        .get("subitem_columns")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter(|column| is_people(column))
+        .collect();
+    let outreach = columns
+        .iter()
+        .find(|column| {
+            column.get("name").and_then(Value::as_str) == Some("People - Sent/Responded")
+        })
+        .or(columns.first())
+        .and_then(|column| column.get("id"))
+        .and_then(Value::as_u64);
+    let Some(outreach) = outreach else {
+        return board;
+    };
+    let key = outreach.to_string();
+    for column in array_mut(&mut board, "subitem_columns") {
+        if column.get("id").and_then(Value::as_u64) != Some(outreach) {
+            continue;
+        }
+        if let Some(column) = column.as_object_mut() {
+            column.insert("kind".to_string(), "Outreach".into());
+            if let Some(default) = column.get_mut("default") {
+                to_outreach(default);
+            }
+        }
+    }
+    for_each_item(&mut board, &mut |item, level| {
+        if level != Level::Subitem {
+            return;
+        }
+        if let Some(value) = item.get_mut("cells").and_then(|cells| cells.get_mut(&key)) {
+            to_outreach(value);
+        }
+    });
+    board
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cell::{CellKind, Contact, FileRef, Money};
+    use crate::column::ColumnDef;
+    use crate::store::Cells;
+    use crate::store::TrashedItem;
+    use chrono::NaiveDate;
+
+    fn load(version: u64, board: Value) -> Board {
+        let board = migrate(version, board).expect("board should migrate");
+        serde_json::from_value(board).expect("migrated board should load")
+    }
+
+    /// The cell of the first column of `kind`.
+    fn cell(columns: &[ColumnDef], cells: &Cells, kind: CellKind) -> CellValue {
+        columns
+            .iter()
+            .find(|column| column.kind == kind)
+            .map(|column| column.cell(cells))
+            .expect("column of that kind")
+    }
+
+    fn v4_columns() -> Value {
+        json!([
+            { "id": 10, "name": "Date", "kind": "Date", "width": 160, "default": { "Date": "2024-08-11" } },
+            { "id": 11, "name": "Area", "kind": "Select", "width": 160, "default": { "Select": "Area 1" }, "options": ["Area 1", "Area 2"] },
+            { "id": 12, "name": "Project Owner", "kind": "Person", "width": 160, "default": { "Person": ["Owner 1"] } },
+            { "id": 13, "name": "Notes", "kind": "LongText", "width": 160, "default": { "LongText": "" } },
+            { "id": 14, "name": "Files / Image Capture", "kind": "Files", "width": 160, "default": { "Files": [] } },
+            { "id": 15, "name": "Budget/Price", "kind": "Money", "width": 160, "default": { "Money": { "cents": 0 } } },
+        ])
+    }
+
+    // The sample board every fixture below holds, as saved by its version:
+    // group "Launch" with task "Plan", its subitem "Draft" and, from v2 on,
+    // a deleted task "Old" in the trash.
+
+    const V3_KEYS: [&str; 6] = ["Date", "Area", "Owner", "Notes", "Files", "Budget"];
+    const TASK_KEYS: [&str; 6] = ["10", "11", "12", "13", "14", "15"];
+    const SUBITEM_KEYS: [&str; 6] = ["16", "17", "18", "19", "20", "21"];
+
+    fn plan_values(owner: Value) -> [Value; 6] {
+        [
+            json!({ "Date": "2024-09-01" }),
+            json!({ "Select": "Area 2" }),
+            json!({ "Person": owner }),
+            json!({ "LongText": "Kickoff" }),
+            json!({ "Files": [] }),
+            json!({ "Money": { "cents": 125050 } }),
+        ]
+    }
+
+    fn draft_values(people: Value) -> [Value; 6] {
+        [
+            json!({ "Date": null }),
+            json!({ "Select": "Area 1" }),
+            json!({ "Person": people }),
+            json!({ "LongText": "" }),
+            json!({ "Files": [{ "name": "brief.pdf" }] }),
+            json!({ "Money": null }),
+        ]
+    }
+
+    fn old_values() -> [Value; 6] {
+        [
+            json!({ "Date": null }),
+            json!({ "Select": null }),
+            json!({ "Person": [] }),
+            json!({ "LongText": "" }),
+            json!({ "Files": [] }),
+            json!({ "Money": { "cents": 500 } }),
+        ]
+    }
+
+    fn cells(keys: [&str; 6], values: [Value; 6]) -> Value {
+        Value::Object(keys.iter().map(|key| key.to_string()).zip(values).collect())
+    }
+
+    /// The sample board laid out as v3 to v7 saved it, from the cells of its
+    /// three items.
+    fn typed_board(plan: Value, draft: Value, old: Value) -> Value {
+        json!({
+            "groups": [{
+                "id": 1,
+                "name": "Launch",
+                "color": "blue",
+                "tasks": [{
+                    "id": 2,
+                    "name": "Plan",
+                    "cells": plan,
+                    "subitems": [{ "id": 3, "name": "Draft", "cells": draft }],
+                }],
+            }],
+            "trash": [{
+                "Task": {
+                    "group": 1,
+                    "index": 1,
+                    "task": { "id": 4, "name": "Old", "cells": old, "subitems": [] },
+                },
+            }],
+            "next_id": 5,
+        })
+    }
+
+    /// Checks a migrated sample board: ids are unique and below `next_id`,
+    /// cells kept their values under typed columns, people are in the
+    /// directory, the subitem people column tracks outreach and the status
+    /// labels and columns are there.
+    fn check_sample(board: &Board, trashed: bool) {
+        let group = &board.groups[0];
+        assert_eq!(
+            (group.name.as_str(), group.color.as_str()),
+            ("Launch", "blue")
+        );
+        let plan = &group.tasks[0];
+        let draft = &plan.subitems[0];
+        assert_eq!((plan.name.as_str(), draft.name.as_str()), ("Plan", "Draft"));
+
+        let mut ids = vec![group.id, plan.id, draft.id];
+        ids.extend(board.columns.iter().map(|column| column.id));
+        ids.extend(board.subitem_columns.iter().map(|column| column.id));
+        ids.extend(board.status_labels.iter().map(|label| label.id));
+        ids.extend(board.people.iter().map(|person| person.id));
+        ids.extend(board.trash.iter().map(|item| item.id()));
+        let next_id = serde_json::to_value(board).expect("board saves")["next_id"]
+            .as_u64()
+            .expect("next_id");
+        assert!(
+            ids.iter().all(|id| *id > 0 && *id < next_id),
+            "{:?} < {}",
+            ids,
+            next_id
+        );
+        ids.sort_unstable();
+        ids.dedup();
+        let count = 3
+            + board.columns.len()
+            + board.subitem_columns.len()
+            + board.status_labels.len()
+            + board.people.len()
+            + board.trash.len();
+        assert_eq!(ids.len(), count);
+
+        let labels: Vec<&str> = board
+            .status_labels
+            .iter()
+            .map(|label| label.name.as_str())
+            .collect();
+        assert_eq!(labels, ["Working on it", "Stuck", "Done"]);
+        assert_eq!(board.columns[3].kind, CellKind::Status);
+        assert_eq!(board.subitem_columns[3].kind, CellKind::Status);
+
+        let person = |name: &str| {
+            board
+                .people
+                .iter()
+                .find(|person| person.name == name)
+                .map(|person| person.id)
+                .unwrap_or_else(|| panic!("{} in directory", name))
+        };
+        let columns = &board.columns;
+        let expect = [
+            (
+                CellKind::Date,
+                CellValue::Date(NaiveDate::from_ymd_opt(2024, 9, 1)),
+            ),
+            (
+                CellKind::Select,
+                CellValue::Select(Some("Area 2".to_string())),
+            ),
+            (
+                CellKind::Person,
+                CellValue::Person(vec![person("Ann"), person("Bo")]),
+            ),
+            (
+                CellKind::LongText,
+                CellValue::LongText("Kickoff".to_string()),
+            ),
+            (CellKind::Files, CellValue::Files(Vec::new())),
+            (
+                CellKind::Money,
+                CellValue::Money(Some(Money { cents: 125050 })),
+            ),
+        ];
+        for (kind, value) in expect {
+            assert_eq!(cell(columns, &plan.cells, kind), value);
+        }
+
+        let columns = &board.subitem_columns;
+        assert!(!columns.iter().any(|column| column.kind == CellKind::Person));
+        let file = FileRef {
+            name: "brief.pdf".to_string(),
+            ..FileRef::default()
+        };
+        let expect = [
+            (CellKind::Date, CellValue::Date(None)),
+            (
+                CellKind::Select,
+                CellValue::Select(Some("Area 1".to_string())),
+            ),
+            (
+                CellKind::Outreach,
+                CellValue::Outreach(vec![Contact::new(person("Cy"))]),
+            ),
+            (CellKind::Files, CellValue::Files(vec![file])),
+            (CellKind::Money, CellValue::Money(None)),
+        ];
+        for (kind, value) in expect {
+            assert_eq!(cell(columns, &draft.cells, kind), value);
+        }
+
+        match (trashed, board.trash.as_slice()) {
+            (false, []) => {}
+            (true, [TrashedItem::Task { group: 1, task, .. }]) => {
+                assert_eq!(task.name, "Old");
+                assert_eq!(
+                    cell(&board.columns, &task.cells, CellKind::Money),
+                    CellValue::Money(Some(Money { cents: 500 }))
+                );
+            }
+            _ => panic!("unexpected trash"),
+        }
+    }
+
+    #[test]
+    fn migrates_v1() {
+        let v1 = json!({
+            "groups": [{
+                "name": "Launch",
+                "color": "blue",
+                "tasks": [{
+                    "name": "Plan",
+                    "date": "2024-09-01",
+                    "area": "Area 2",
+                    "owner": "Ann, Bo",
+                    "notes": "Kickoff",
+                    "files": "2",
+                    "budget": "$1,250.50",
+                    "subitems": [{
+                        "name": "Draft",
+                        "date": "soon",
+                        "area": "Area 1",
+                        "people": "Cy",
+                        "notes": "",
+                        "files": "brief.pdf",
+                        "budget": "",
+                    }],
+                }],
+            }],
+        });
+        check_sample(&load(1, v1), false);
+    }
+
+    #[test]
+    fn migrates_v2() {
+        // Tasks name their people "owner", subitems "people".
+        let item = |id: u64, name: &str, people: &str, text: [&str; 6]| {
+            let mut item = json!({ "id": id, "name": name, "subitems": [] });
+            for (key, value) in ["date", "area", people, "notes", "files", "budget"]
+                .into_iter()
+                .zip(text)
+            {
+                item[key] = value.into();
+            }
+            item
+        };
+        let mut plan = item(
+            2,
+            "Plan",
+            "owner",
+            [
+                "2024-09-01",
+                "Area 2",
+                "Ann, Bo",
+                "Kickoff",
+                "2",
+                "$1,250.50",
+            ],
+        );
+        plan["subitems"] = json!([item(
+            3,
+            "Draft",
+            "people",
+            ["soon", "Area 1", "Cy", "", "brief.pdf", ""]
+        )]);
+        let old = item(4, "Old", "owner", ["", "", "", "", "0", "$5"]);
+        let v2 = json!({
+            "groups": [{ "id": 1, "name": "Launch", "color": "blue", "tasks": [plan] }],
+            "trash": [{ "Task": { "group": 1, "index": 1, "task": old } }],
+            "next_id": 5,
+        });
+        check_sample(&load(2, v2), true);
+    }
+
+    #[test]
+    fn migrates_v3() {
+        let v3 = typed_board(
+            cells(V3_KEYS, plan_values(json!(["Ann", "Bo"]))),
+            cells(V3_KEYS, draft_values(json!(["Cy"]))),
+            cells(V3_KEYS, old_values()),
+        );
+        check_sample(&load(3, v3), true);
+    }
+
+    #[test]
+    fn migrates_v4() {
+        let mut v4 = typed_board(
+            cells(TASK_KEYS, plan_values(json!(["Ann", "Bo"]))),
+            cells(TASK_KEYS, draft_values(json!(["Cy"]))),
+            cells(TASK_KEYS, old_values()),
+        );
+        v4["columns"] = v4_columns();
+        v4["next_id"] = 16.into();
+        check_sample(&load(4, v4), true);
+    }
+
+    /// v5 columns: the task ones, and the subitem ones as new boards had them.
+    fn v5_board(plan: [Value; 6], draft: [Value; 6]) -> Value {
+        let mut board = typed_board(
+            cells(TASK_KEYS, plan),
+            cells(SUBITEM_KEYS, draft),
+            cells(TASK_KEYS, old_values()),
+        );
+        board["columns"] = v4_columns();
+        let mut subitem_columns = v4_columns();
+        for (column, id) in subitem_columns
+            .as_array_mut()
+            .into_iter()
+            .flatten()
+            .zip(16..)
+        {
+            column["id"] = id.into();
+        }
+        subitem_columns[2]["name"] = "People - Sent/Responded".into();
+        board["subitem_columns"] = subitem_columns;
+        board["next_id"] = 22.into();
+        board
+    }
+
+    #[test]
+    fn migrates_v5() {
+        let v5 = v5_board(
+            plan_values(json!(["Ann", "Bo"])),
+            draft_values(json!(["Cy"])),
+        );
+        check_sample(&load(5, v5), true);
+    }
+
+    /// v6 adds status columns 22 and 23 and labels 24 to 26. Plan is
+    /// "Stuck".
+    fn v6_board(plan: [Value; 6], draft: [Value; 6]) -> Value {
+        let mut board = v5_board(plan, draft);
+        let status = |id: u64| json!({ "id": id, "name": "Status", "kind": "Status", "width": 160, "default": { "Status": null } });
+        for (key, id) in [("columns", 22), ("subitem_columns", 23)] {
+            if let Some(columns) = board[key].as_array_mut() {
+                columns.insert(3, status(id));
+            }
+        }
+        board["status_labels"] = json!([
+            { "id": 24, "name": "Working on it", "color": "orange" },
+            { "id": 25, "name": "Stuck", "color": "red" },
+            { "id": 26, "name": "Done", "color": "green" },
+        ]);
+        board["groups"][0]["tasks"][0]["cells"]["22"] = json!({ "Status": 25 });
+        board["next_id"] = 27.into();
+        board
+    }
+
+    fn check_stuck(board: &Board) {
+        let plan = board.task(2).expect("task kept");
+        assert_eq!(
+            board.columns[3].cell(&plan.cells),
+            CellValue::Status(Some(25))
+        );
+        assert_eq!(board.status_labels[1].id, 25);
+    }
+
+    #[test]
+    fn migrates_v6() {
+        let v6 = v6_board(
+            plan_values(json!(["Ann", "Bo"])),
+            draft_values(json!(["Cy"])),
+        );
+        let board = load(6, v6);
+        check_sample(&board, true);
+        check_stuck(&board);
+    }
+
+    #[test]
+    fn migrates_v7() {
+        let mut v7 = v6_board(plan_values(json!([27, 28])), draft_values(json!([29])));
+        v7["people"] = json!([
+            { "id": 27, "name": "Ann", "initials": "A", "color": "red" },
+            { "id": 28, "name": "Bo", "initials": "B", "color": "blue" },
+            { "id": 29, "name": "Cy", "initials": "C", "color": "green" },
+        ]);
+        for key in ["columns", "subitem_columns"] {
+            for column in array_mut(&mut v7, key) {
+                if column["kind"] == "Person" {
+                    column["default"] = json!({ "Person": [] });
+                }
+            }
+        }
+        v7["next_id"] = 30.into();
+        let board = load(7, v7);
+        check_sample(&board, true);
+        check_stuck(&board);
+        assert_eq!(board.people.len(), 3);
+    }
+
+    #[test]
+    fn current_version_is_kept_and_unknown_ones_fail() {
+        let board = json!({ "groups": [] });
+        assert_eq!(migrate(SCHEMA_VERSION, board.clone()), Ok(board.clone()));
+        assert!(migrate(0, board.clone()).is_err());
+        assert!(migrate(SCHEMA_VERSION + 1, board).is_err());
+    }
+
+    #[test]
+    fn v4_subitem_cells_survive() {
+        let v4 = json!({
+            "groups": [{
+                "id": 1,
+                "name": "Launch",
+                "color": "blue",
+                "tasks": [{
+                    "id": 2,
+                    "name": "Plan",
+                    "cells": { "10": { "Date": "2024-09-01" } },
+                    "subitems": [{
+                        "id": 3,
+                        "name": "Draft",
+                        "cells": {
+                            "10": { "Date": "2024-09-02" },
+                            "12": { "Person": ["Ann"] },
+                            "13": { "LongText": "Outline first" },
+                            "15": { "Money": { "cents": 1250 } },
+                        },
+                    }],
+                }],
+            }],
+            "columns": v4_columns(),
+            "trash": [],
+            "next_id": 16,
+        });
+        let board = load(4, v4);
+        let subitem = board.subitem(3).expect("subitem kept");
+        let columns = &board.subitem_columns;
+        assert!(columns.iter().all(|column| column.id >= 16));
+        assert_eq!(columns.len(), 7);
+        assert_eq!(columns[3].kind, CellKind::Status);
+        assert_eq!(board.columns[3].kind, CellKind::Status);
+        assert_eq!(
+            cell(columns, &subitem.cells, CellKind::Status),
+            CellValue::Status(None)
+        );
+        assert_eq!(
+            cell(columns, &subitem.cells, CellKind::Date),
+            CellValue::Date(NaiveDate::from_ymd_opt(2024, 9, 2))
+        );
+        assert_eq!(
+            cell(columns, &subitem.cells, CellKind::LongText),
+            CellValue::LongText("Outline first".to_string())
+        );
+        assert_eq!(
+            cell(columns, &subitem.cells, CellKind::Money),
+            CellValue::Money(Some(Money { cents: 1250 }))
+        );
+        assert!(board.people.iter().any(|person| person.name == "Ann"));
+    }
+
+    #[test]
+    fn copied_people_column_tracks_outreach() {
+        let v4 = json!({
+            "groups": [{
+                "id": 1,
+                "name": "Launch",
+                "color": "blue",
+                "tasks": [{
+                    "id": 2,
+                    "name": "Plan",
+                    "cells": { "12": { "Person": ["Ann"] } },
+                    "subitems": [{ "id": 3, "name": "Draft", "cells": { "12": { "Person": ["Ann", "Bo"] } } }],
+                }],
+            }],
+            "columns": v4_columns(),
+            "trash": [],
+            "next_id": 16,
+        });
+        let board = load(4, v4);
+        let person = |name: &str| {
+            board
+                .people
+                .iter()
+                .find(|person| person.name == name)
+                .map(|person| person.id)
+                .expect("person in directory")
+        };
+        // Tasks keep their people column; the subitem copy tracks outreach.
+        assert_eq!(
+            cell(
+                &board.columns,
+                &board.task(2).expect("task kept").cells,
+                CellKind::Person
+            ),
+            CellValue::Person(vec![person("Ann")])
+        );
+        let outreach: Vec<_> = board
+            .subitem_columns
+            .iter()
+            .filter(|column| column.kind == CellKind::Outreach)
+            .collect();
+        assert_eq!(outreach.len(), 1);
+        assert_eq!(outreach[0].name, "Project Owner");
+        assert!(!board
+            .subitem_columns
+            .iter()
+            .any(|column| column.kind == CellKind::Person));
+        assert_eq!(
+            outreach[0].cell(&board.subitem(3).expect("subitem kept").cells),
+            CellValue::Outreach(vec![
+                Contact::new(person("Ann")),
+                Contact::new(person("Bo"))
+            ])
+        );
+    }
+
+    #[test]
+    fn missing_columns_fail_the_migration() {
+        let v4 = json!({ "groups": [], "trash": [], "next_id": 1 });
+        assert!(migrate(4, v4).is_err());
+    }
+}
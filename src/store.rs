@@ -0,0 +1,1194 @@
+use crate::cell::{CellKind, CellValue, Contact, Currency, FileRef};
+use crate::column::{default_cells, standard_columns, ColumnDef, ColumnScope};
+use crate::people::Person;
+use crate::sort::{SortColumn, SortKey};
+use crate::status::{default_status_labels, StatusLabel};
+use crate::summary::Aggregate;
+use crate::updates::Update;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
+use std::ops::Deref;
+use std::rc::Rc;
+use yew::prelude::*;
+
+/// Stable identifier for groups, tasks and subitems. Ids are handed out by
+/// [`Board::next_id`] and never reused, so they stay valid across reorders
+/// and deletes.
+pub type Id = u64;
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct GroupData {
+    pub id: Id,
+    pub name: String,
+    pub color: String,
+    pub tasks: Vec<TaskData>,
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct TaskData {
+    pub id: Id,
+    pub name: String,
+    pub cells: Cells,
+    pub subitems: Vec<SubitemData>,
+    /// Comments posted on the task, oldest first.
+    #[serde(default)]
+    pub updates: Vec<Update>,
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct SubitemData {
+    pub id: Id,
+    pub name: String,
+    pub cells: Cells,
+    /// Comments posted on the subitem, oldest first.
+    #[serde(default)]
+    pub updates: Vec<Update>,
+}
+
+/// Cell values keyed by [`ColumnDef::id`].
+pub type Cells = BTreeMap<Id, CellValue>;
+
+impl TaskData {
+    pub fn new(id: Id, name: String, cells: Cells) -> Self {
+        Self {
+            id,
+            name,
+            cells,
+            subitems: Vec::new(),
+            updates: Vec::new(),
+        }
+    }
+}
+
+impl SubitemData {
+    pub fn new(id: Id, name: String, cells: Cells) -> Self {
+        Self {
+            id,
+            name,
+            cells,
+            updates: Vec::new(),
+        }
+    }
+}
+
+/// Something deleted from the board, remembering where it lived so it can
+/// be put back.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub enum TrashedItem {
+    Group {
+        index: usize,
+        group: GroupData,
+    },
+    Task {
+        group: Id,
+        index: usize,
+        task: TaskData,
+    },
+    Subitem {
+        task: Id,
+        index: usize,
+        subitem: SubitemData,
+    },
+}
+
+impl TrashedItem {
+    pub fn id(&self) -> Id {
+        match self {
+            TrashedItem::Group { group, .. } => group.id,
+            TrashedItem::Task { task, .. } => task.id,
+            TrashedItem::Subitem { subitem, .. } => subitem.id,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            TrashedItem::Group { group, .. } => &group.name,
+            TrashedItem::Task { task, .. } => &task.name,
+            TrashedItem::Subitem { subitem, .. } => &subitem.name,
+        }
+    }
+}
+
+/// The whole board. Every edit goes through [`BoardAction`] so that group
+/// counters and expanded/collapsed rows always render from the same model.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct Board {
+    pub groups: Vec<GroupData>,
+    /// Columns shown after the name on every task, in order.
+    pub columns: Vec<ColumnDef>,
+    /// Columns shown after the name on every subitem, in order.
+    pub subitem_columns: Vec<ColumnDef>,
+    /// Choices offered by every status column.
+    pub status_labels: Vec<StatusLabel>,
+    /// Everyone person cells can list.
+    pub people: Vec<Person>,
+    /// Who is using the board, for filters like "owner is me".
+    #[serde(default)]
+    pub me: Option<Id>,
+    /// How every group orders its tasks until someone sorts it differently.
+    /// Empty keeps the order tasks were added in.
+    #[serde(default)]
+    pub default_sort: Vec<SortKey>,
+    #[serde(default)]
+    pub trash: Vec<TrashedItem>,
+    next_id: Id,
+}
+
+impl Default for Board {
+    fn default() -> Self {
+        let mut board = Self {
+            groups: Vec::new(),
+            columns: Vec::new(),
+            subitem_columns: Vec::new(),
+            status_labels: Vec::new(),
+            people: Vec::new(),
+            me: None,
+            default_sort: Vec::new(),
+            trash: Vec::new(),
+            next_id: 1,
+        };
+        for (name, color) in [
+            ("This is Sample Group 1", "purple"),
+            ("This is Sample Group 2", "red"),
+        ] {
+            let id = board.next_id();
+            board.groups.push(GroupData {
+                id,
+                name: name.to_string(),
+                color: color.to_string(),
+                tasks: Vec::new(),
+            });
+        }
+        board.columns = standard_columns(ColumnScope::Task, || board.next_id());
+        board.subitem_columns = standard_columns(ColumnScope::Subitem, || board.next_id());
+        for scope in [ColumnScope::Task, ColumnScope::Subitem] {
+            let id = board.next_id();
+            board.columns_for_mut(scope).insert(
+                3,
+                ColumnDef::new(id, "Status".to_string(), CellKind::Status),
+            );
+        }
+        board.status_labels = default_status_labels(|| board.next_id());
+        for (scope, name) in [
+            (ColumnScope::Task, "Owner 1"),
+            (ColumnScope::Subitem, "Person 1"),
+        ] {
+            let id = board.next_id();
+            let index = board.people.len();
+            board
+                .people
+                .push(Person::new(id, name.to_string(), String::new(), index));
+            for column in board.columns_for_mut(scope) {
+                match column.kind {
+                    CellKind::Person => column.default = CellValue::Person(vec![id]),
+                    CellKind::Outreach => {
+                        column.default = CellValue::Outreach(vec![Contact::new(id)])
+                    }
+                    _ => {}
+                }
+            }
+        }
+        board
+    }
+}
+
+pub enum BoardAction {
+    AddGroup {
+        name: String,
+        color: String,
+    },
+    RenameGroup {
+        group: Id,
+        name: String,
+    },
+    AddTask {
+        group: Id,
+        name: String,
+    },
+    RenameTask {
+        task: Id,
+        name: String,
+    },
+    UpdateTask {
+        task: Id,
+        column: Id,
+        value: CellValue,
+    },
+    AddSubitem {
+        task: Id,
+        name: String,
+    },
+    RenameSubitem {
+        subitem: Id,
+        name: String,
+    },
+    UpdateSubitem {
+        subitem: Id,
+        column: Id,
+        value: CellValue,
+    },
+    /// Adds uploaded files to the end of a task's or subitem's files cell,
+    /// keeping whatever was attached while they uploaded. With no `column`,
+    /// they go to the first files column the board has once they are done.
+    AttachFiles {
+        scope: ColumnScope,
+        item: Id,
+        column: Option<Id>,
+        files: Vec<FileRef>,
+    },
+    AddColumn {
+        scope: ColumnScope,
+        name: String,
+        kind: CellKind,
+    },
+    RenameColumn {
+        column: Id,
+        name: String,
+    },
+    /// Moves a column to position `to` among the columns of its scope.
+    MoveColumn {
+        column: Id,
+        to: usize,
+    },
+    SetColumnWidth {
+        column: Id,
+        width: u32,
+    },
+    SetColumnDefault {
+        column: Id,
+        value: CellValue,
+    },
+    SetColumnOptions {
+        column: Id,
+        options: Vec<String>,
+    },
+    /// Relabels a money column's amounts in another currency, unconverted.
+    SetColumnCurrency {
+        column: Id,
+        currency: Currency,
+    },
+    SetColumnSummary {
+        column: Id,
+        aggregate: Aggregate,
+    },
+    /// Drops a column along with every item's value for it.
+    RemoveColumn {
+        column: Id,
+    },
+    AddStatusLabel {
+        name: String,
+        color: String,
+    },
+    RenameStatusLabel {
+        label: Id,
+        name: String,
+    },
+    SetStatusLabelColor {
+        label: Id,
+        color: String,
+    },
+    /// Drops a label. Cells that used it show no status.
+    RemoveStatusLabel {
+        label: Id,
+    },
+    AddPerson {
+        name: String,
+        email: String,
+    },
+    RenamePerson {
+        person: Id,
+        name: String,
+    },
+    SetPersonInitials {
+        person: Id,
+        initials: String,
+    },
+    SetPersonColor {
+        person: Id,
+        color: String,
+    },
+    SetPersonEmail {
+        person: Id,
+        email: String,
+    },
+    /// Drops someone from the directory and from every person cell.
+    RemovePerson {
+        person: Id,
+    },
+    SetMe {
+        person: Option<Id>,
+    },
+    SetDefaultSort {
+        sort: Vec<SortKey>,
+    },
+    /// Adds a comment to the end of a task's or subitem's updates.
+    PostUpdate {
+        scope: ColumnScope,
+        item: Id,
+        author: Option<Id>,
+        body: String,
+        posted: DateTime<Utc>,
+    },
+    DeleteUpdate {
+        update: Id,
+    },
+    DeleteGroup {
+        group: Id,
+    },
+    DeleteTask {
+        task: Id,
+    },
+    DeleteSubitem {
+        subitem: Id,
+    },
+    /// Puts a trashed item back where it was deleted from.
+    Restore {
+        id: Id,
+    },
+    /// Removes a trashed item for good.
+    Purge {
+        id: Id,
+    },
+    EmptyTrash,
+    Undo,
+    Redo,
+}
+
+impl Board {
+    pub fn group(&self, id: Id) -> Option<&GroupData> {
+        self.groups.iter().find(|group| group.id == id)
+    }
+
+    pub fn task(&self, id: Id) -> Option<&TaskData> {
+        self.groups
+            .iter()
+            .flat_map(|group| group.tasks.iter())
+            .find(|task| task.id == id)
+    }
+
+    pub fn subitem(&self, id: Id) -> Option<&SubitemData> {
+        self.groups
+            .iter()
+            .flat_map(|group| group.tasks.iter())
+            .flat_map(|task| task.subitems.iter())
+            .find(|subitem| subitem.id == id)
+    }
+
+    pub fn person(&self, id: Id) -> Option<&Person> {
+        self.people.iter().find(|person| person.id == id)
+    }
+
+    fn group_mut(&mut self, id: Id) -> Option<&mut GroupData> {
+        self.groups.iter_mut().find(|group| group.id == id)
+    }
+
+    fn task_mut(&mut self, id: Id) -> Option<&mut TaskData> {
+        self.groups
+            .iter_mut()
+            .flat_map(|group| group.tasks.iter_mut())
+            .find(|task| task.id == id)
+    }
+
+    fn subitem_mut(&mut self, id: Id) -> Option<&mut SubitemData> {
+        self.groups
+            .iter_mut()
+            .flat_map(|group| group.tasks.iter_mut())
+            .flat_map(|task| task.subitems.iter_mut())
+            .find(|subitem| subitem.id == id)
+    }
+
+    /// A trashed task or subitem can only go back if its parent is still on
+    /// the board.
+    pub fn can_restore(&self, item: &TrashedItem) -> bool {
+        match item {
+            TrashedItem::Group { .. } => true,
+            TrashedItem::Task { group, .. } => self.group(*group).is_some(),
+            TrashedItem::Subitem { task, .. } => self.task(*task).is_some(),
+        }
+    }
+
+    fn delete_group(&mut self, id: Id) {
+        if let Some(index) = self.groups.iter().position(|group| group.id == id) {
+            let group = self.groups.remove(index);
+            self.trash.push(TrashedItem::Group { index, group });
+        }
+    }
+
+    fn delete_task(&mut self, id: Id) {
+        for group in self.groups.iter_mut() {
+            if let Some(index) = group.tasks.iter().position(|task| task.id == id) {
+                let task = group.tasks.remove(index);
+                self.trash.push(TrashedItem::Task {
+                    group: group.id,
+                    index,
+                    task,
+                });
+                return;
+            }
+        }
+    }
+
+    fn delete_subitem(&mut self, id: Id) {
+        for task in self
+            .groups
+            .iter_mut()
+            .flat_map(|group| group.tasks.iter_mut())
+        {
+            if let Some(index) = task.subitems.iter().position(|subitem| subitem.id == id) {
+                let subitem = task.subitems.remove(index);
+                self.trash.push(TrashedItem::Subitem {
+                    task: task.id,
+                    index,
+                    subitem,
+                });
+                return;
+            }
+        }
+    }
+
+    fn restore(&mut self, id: Id) {
+        let Some(position) = self.trash.iter().position(|item| item.id() == id) else {
+            return;
+        };
+        if !self.can_restore(&self.trash[position]) {
+            return;
+        }
+        match self.trash.remove(position) {
+            TrashedItem::Group { index, group } => {
+                let index = index.min(self.groups.len());
+                self.groups.insert(index, group);
+            }
+            TrashedItem::Task {
+                group: group_id,
+                index,
+                task,
+            } => {
+                if let Some(group) = self.group_mut(group_id) {
+                    let index = index.min(group.tasks.len());
+                    group.tasks.insert(index, task);
+                }
+            }
+            TrashedItem::Subitem {
+                task: task_id,
+                index,
+                subitem,
+            } => {
+                if let Some(task) = self.task_mut(task_id) {
+                    let index = index.min(task.subitems.len());
+                    task.subitems.insert(index, subitem);
+                }
+            }
+        }
+    }
+
+    pub fn columns_for(&self, scope: ColumnScope) -> &[ColumnDef] {
+        match scope {
+            ColumnScope::Task => &self.columns,
+            ColumnScope::Subitem => &self.subitem_columns,
+        }
+    }
+
+    /// The first column of `kind` in `scope`. Panics if there is none.
+    #[cfg(test)]
+    pub fn column_of_kind(&self, scope: ColumnScope, kind: CellKind) -> Id {
+        self.columns_for(scope)
+            .iter()
+            .find(|column| column.kind == kind)
+            .unwrap()
+            .id
+    }
+
+    fn columns_for_mut(&mut self, scope: ColumnScope) -> &mut Vec<ColumnDef> {
+        match scope {
+            ColumnScope::Task => &mut self.columns,
+            ColumnScope::Subitem => &mut self.subitem_columns,
+        }
+    }
+
+    fn column_mut(&mut self, id: Id) -> Option<&mut ColumnDef> {
+        self.columns
+            .iter_mut()
+            .chain(self.subitem_columns.iter_mut())
+            .find(|column| column.id == id)
+    }
+
+    fn column_scope(&self, id: Id) -> Option<ColumnScope> {
+        [ColumnScope::Task, ColumnScope::Subitem]
+            .into_iter()
+            .find(|scope| {
+                self.columns_for(*scope)
+                    .iter()
+                    .any(|column| column.id == id)
+            })
+    }
+
+    /// Whether `value` can be stored in the given column of `scope`.
+    fn fits(&self, scope: ColumnScope, column: Id, value: &CellValue) -> bool {
+        self.columns_for(scope)
+            .iter()
+            .any(|c| c.id == column && c.kind == value.kind())
+    }
+
+    fn move_column(&mut self, id: Id, to: usize) {
+        let Some(scope) = self.column_scope(id) else {
+            return;
+        };
+        let columns = self.columns_for_mut(scope);
+        if let Some(from) = columns.iter().position(|column| column.id == id) {
+            let column = columns.remove(from);
+            let to = to.min(columns.len());
+            columns.insert(to, column);
+        }
+    }
+
+    fn remove_column(&mut self, id: Id) {
+        let Some(scope) = self.column_scope(id) else {
+            return;
+        };
+        self.columns_for_mut(scope).retain(|column| column.id != id);
+        self.default_sort
+            .retain(|key| key.column != SortColumn::Column(id));
+        for task in self
+            .groups
+            .iter_mut()
+            .flat_map(|group| group.tasks.iter_mut())
+        {
+            match scope {
+                ColumnScope::Task => {
+                    task.cells.remove(&id);
+                }
+                ColumnScope::Subitem => {
+                    for subitem in task.subitems.iter_mut() {
+                        subitem.cells.remove(&id);
+                    }
+                }
+            }
+        }
+    }
+
+    fn status_label_mut(&mut self, id: Id) -> Option<&mut StatusLabel> {
+        self.status_labels.iter_mut().find(|label| label.id == id)
+    }
+
+    fn updates_mut(&mut self, scope: ColumnScope, item: Id) -> Option<&mut Vec<Update>> {
+        match scope {
+            ColumnScope::Task => self.task_mut(item).map(|task| &mut task.updates),
+            ColumnScope::Subitem => self.subitem_mut(item).map(|subitem| &mut subitem.updates),
+        }
+    }
+
+    fn cells_mut(&mut self, scope: ColumnScope, item: Id) -> Option<&mut Cells> {
+        match scope {
+            ColumnScope::Task => self.task_mut(item).map(|task| &mut task.cells),
+            ColumnScope::Subitem => self.subitem_mut(item).map(|subitem| &mut subitem.cells),
+        }
+    }
+
+    fn delete_update(&mut self, id: Id) {
+        for task in self
+            .groups
+            .iter_mut()
+            .flat_map(|group| group.tasks.iter_mut())
+        {
+            task.updates.retain(|update| update.id != id);
+            for subitem in task.subitems.iter_mut() {
+                subitem.updates.retain(|update| update.id != id);
+            }
+        }
+    }
+
+    fn person_mut(&mut self, id: Id) -> Option<&mut Person> {
+        self.people.iter_mut().find(|person| person.id == id)
+    }
+
+    fn remove_person(&mut self, id: Id) {
+        self.people.retain(|person| person.id != id);
+        if self.me == Some(id) {
+            self.me = None;
+        }
+        let unassign = |value: &mut CellValue| match value {
+            CellValue::Person(people) => people.retain(|person| *person != id),
+            CellValue::Outreach(contacts) => contacts.retain(|contact| contact.person != id),
+            _ => {}
+        };
+        for column in self
+            .columns
+            .iter_mut()
+            .chain(self.subitem_columns.iter_mut())
+        {
+            unassign(&mut column.default);
+        }
+        for task in self
+            .groups
+            .iter_mut()
+            .flat_map(|group| group.tasks.iter_mut())
+        {
+            task.cells.values_mut().for_each(unassign);
+            for subitem in task.subitems.iter_mut() {
+                subitem.cells.values_mut().for_each(unassign);
+            }
+        }
+    }
+
+    fn next_id(&mut self) -> Id {
+        let id = self.next_id;
+        self.next_id += 1;
+        id
+    }
+
+    fn apply(&mut self, action: BoardAction) {
+        match action {
+            BoardAction::AddGroup { name, color } => {
+                let id = self.next_id();
+                self.groups.push(GroupData {
+                    id,
+                    name,
+                    color,
+                    tasks: Vec::new(),
+                });
+            }
+            BoardAction::RenameGroup { group, name } => {
+                if let Some(group) = self.group_mut(group) {
+                    group.name = name;
+                }
+            }
+            BoardAction::AddTask { group, name } => {
+                let id = self.next_id();
+                let cells = default_cells(&self.columns);
+                if let Some(group) = self.group_mut(group) {
+                    group.tasks.push(TaskData::new(id, name, cells));
+                }
+            }
+            BoardAction::RenameTask { task, name } => {
+                if let Some(task) = self.task_mut(task) {
+                    task.name = name;
+                }
+            }
+            BoardAction::UpdateTask {
+                task,
+                column,
+                value,
+            } => {
+                if self.fits(ColumnScope::Task, column, &value) {
+                    if let Some(task) = self.task_mut(task) {
+                        task.cells.insert(column, value);
+                    }
+                }
+            }
+            BoardAction::AddSubitem { task, name } => {
+                let id = self.next_id();
+                let cells = default_cells(&self.subitem_columns);
+                if let Some(task) = self.task_mut(task) {
+                    task.subitems.push(SubitemData::new(id, name, cells));
+                }
+            }
+            BoardAction::RenameSubitem { subitem, name } => {
+                if let Some(subitem) = self.subitem_mut(subitem) {
+                    subitem.name = name;
+                }
+            }
+            BoardAction::UpdateSubitem {
+                subitem,
+                column,
+                value,
+            } => {
+                if self.fits(ColumnScope::Subitem, column, &value) {
+                    if let Some(subitem) = self.subitem_mut(subitem) {
+                        subitem.cells.insert(column, value);
+                    }
+                }
+            }
+            BoardAction::AttachFiles {
+                scope,
+                item,
+                column,
+                files,
+            } => {
+                let column = self
+                    .columns_for(scope)
+                    .iter()
+                    .find(|c| {
+                        c.kind == CellKind::Files && (column.is_none() || column == Some(c.id))
+                    })
+                    .cloned();
+                if let (Some(column), Some(cells)) = (column, self.cells_mut(scope, item)) {
+                    if let CellValue::Files(mut attached) = column.cell(cells) {
+                        attached.extend(files);
+                        cells.insert(column.id, CellValue::Files(attached));
+                    }
+                }
+            }
+            BoardAction::AddColumn { scope, name, kind } => {
+                let id = self.next_id();
+                self.columns_for_mut(scope)
+                    .push(ColumnDef::new(id, name, kind));
+            }
+            BoardAction::RenameColumn { column, name } => {
+                if let Some(column) = self.column_mut(column) {
+                    column.name = name;
+                }
+            }
+            BoardAction::MoveColumn { column, to } => self.move_column(column, to),
+            BoardAction::SetColumnWidth { column, width } => {
+                if let Some(column) = self.column_mut(column) {
+                    column.width = width;
+                }
+            }
+            BoardAction::SetColumnDefault { column, value } => {
+                if let Some(column) = self.column_mut(column) {
+                    // Every new item would share the stored files.
+                    if column.kind == value.kind() && column.kind != CellKind::Files {
+                        column.default = value;
+                    }
+                }
+            }
+            BoardAction::SetColumnOptions { column, options } => {
+                if let Some(column) = self.column_mut(column) {
+                    column.options = options;
+                }
+            }
+            BoardAction::SetColumnCurrency { column, currency } => {
+                if let Some(column) = self.column_mut(column) {
+                    column.currency = currency;
+                }
+            }
+            BoardAction::SetColumnSummary { column, aggregate } => {
+                if let Some(column) = self.column_mut(column) {
+                    column.summary = Some(aggregate);
+                }
+            }
+            BoardAction::RemoveColumn { column } => self.remove_column(column),
+            BoardAction::AddStatusLabel { name, color } => {
+                let id = self.next_id();
+                self.status_labels.push(StatusLabel { id, name, color });
+            }
+            BoardAction::RenameStatusLabel { label, name } => {
+                if let Some(label) = self.status_label_mut(label) {
+                    label.name = name;
+                }
+            }
+            BoardAction::SetStatusLabelColor { label, color } => {
+                if let Some(label) = self.status_label_mut(label) {
+                    label.color = color;
+                }
+            }
+            BoardAction::RemoveStatusLabel { label } => {
+                self.status_labels.retain(|l| l.id != label);
+            }
+            BoardAction::AddPerson { name, email } => {
+                let id = self.next_id();
+                let index = self.people.len();
+                self.people.push(Person::new(id, name, email, index));
+            }
+            BoardAction::RenamePerson { person, name } => {
+                if let Some(person) = self.person_mut(person) {
+                    person.name = name;
+                }
+            }
+            BoardAction::SetPersonInitials { person, initials } => {
+                if let Some(person) = self.person_mut(person) {
+                    person.initials = initials;
+                }
+            }
+            BoardAction::SetPersonColor { person, color } => {
+                if let Some(person) = self.person_mut(person) {
+                    person.color = color;
+                }
+            }
+            BoardAction::SetPersonEmail { person, email } => {
+                if let Some(person) = self.person_mut(person) {
+                    person.email = email;
+                }
+            }
+            BoardAction::RemovePerson { person } => self.remove_person(person),
+            BoardAction::SetMe { person } => self.me = person,
+            BoardAction::SetDefaultSort { sort } => self.default_sort = sort,
+            BoardAction::PostUpdate {
+                scope,
+                item,
+                author,
+                body,
+                posted,
+            } => {
+                let id = self.next_id();
+                if let Some(updates) = self.updates_mut(scope, item) {
+                    updates.push(Update {
+                        id,
+                        author,
+                        body,
+                        posted,
+                    });
+                }
+            }
+            BoardAction::DeleteUpdate { update } => self.delete_update(update),
+            BoardAction::DeleteGroup { group } => self.delete_group(group),
+            BoardAction::DeleteTask { task } => self.delete_task(task),
+            BoardAction::DeleteSubitem { subitem } => self.delete_subitem(subitem),
+            BoardAction::Restore { id } => self.restore(id),
+            BoardAction::Purge { id } => self.trash.retain(|item| item.id() != id),
+            BoardAction::EmptyTrash => self.trash.clear(),
+            // History is kept by `BoardStore`, not the board itself.
+            BoardAction::Undo | BoardAction::Redo => {}
+        }
+    }
+}
+
+/// How many edits can be undone before the oldest ones are dropped.
+const HISTORY_LIMIT: usize = 100;
+
+/// The reducer behind the board context: the current [`Board`] plus snapshots
+/// for undo/redo. Derefs to the board so components can read it directly.
+#[derive(PartialEq)]
+pub struct BoardStore {
+    pub board: Rc<Board>,
+    undo: VecDeque<Rc<Board>>,
+    redo: Vec<Rc<Board>>,
+}
+
+impl BoardStore {
+    pub fn new(board: Board) -> Self {
+        Self {
+            board: Rc::new(board),
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+        }
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+}
+
+impl Deref for BoardStore {
+    type Target = Board;
+
+    fn deref(&self) -> &Board {
+        &self.board
+    }
+}
+
+impl Reducible for BoardStore {
+    type Action = BoardAction;
+
+    fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+        let mut undo = self.undo.clone();
+        let mut redo = self.redo.clone();
+        let board = match action {
+            BoardAction::Undo => {
+                let Some(previous) = undo.pop_back() else {
+                    return self;
+                };
+                redo.push(self.board.clone());
+                previous
+            }
+            BoardAction::Redo => {
+                let Some(next) = redo.pop() else {
+                    return self;
+                };
+                undo.push_back(self.board.clone());
+                next
+            }
+            action => {
+                let mut board = (*self.board).clone();
+                board.apply(action);
+                if board == *self.board {
+                    return self;
+                }
+                undo.push_back(self.board.clone());
+                if undo.len() > HISTORY_LIMIT {
+                    undo.pop_front();
+                }
+                redo.clear();
+                Rc::new(board)
+            }
+        };
+        Rc::new(BoardStore { board, undo, redo })
+    }
+}
+
+pub type BoardHandle = UseReducerHandle<BoardStore>;
+
+#[hook]
+pub fn use_board() -> BoardHandle {
+    use_context::<BoardHandle>().expect("board store must be provided by App")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cell::Money;
+
+    fn file(name: &str) -> FileRef {
+        FileRef {
+            name: name.to_string(),
+            key: Some(name.to_string()),
+            ..FileRef::default()
+        }
+    }
+
+    /// Adds a task with one subitem to the first group.
+    fn task_with_subitem(board: &mut Board) -> (Id, Id) {
+        let group = board.groups[0].id;
+        board.apply(BoardAction::AddTask {
+            group,
+            name: "Task".to_string(),
+        });
+        let task = board.groups[0].tasks.last().unwrap().id;
+        board.apply(BoardAction::AddSubitem {
+            task,
+            name: "Subitem".to_string(),
+        });
+        let subitem = board.task(task).unwrap().subitems[0].id;
+        (task, subitem)
+    }
+
+    fn task_names(board: &Board) -> Vec<&str> {
+        board.groups[0]
+            .tasks
+            .iter()
+            .map(|task| task.name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn restore_puts_items_back_where_they_were() {
+        let mut board = Board::default();
+        let group = board.groups[0].id;
+        for name in ["A", "B", "C"] {
+            board.apply(BoardAction::AddTask {
+                group,
+                name: name.to_string(),
+            });
+        }
+        let b = board.groups[0].tasks[1].id;
+        board.apply(BoardAction::DeleteTask { task: b });
+        assert_eq!(task_names(&board), ["A", "C"]);
+        board.apply(BoardAction::Restore { id: b });
+        assert_eq!(task_names(&board), ["A", "B", "C"]);
+        assert!(board.trash.is_empty());
+
+        board.apply(BoardAction::DeleteGroup { group });
+        assert_ne!(board.groups[0].id, group);
+        board.apply(BoardAction::Restore { id: group });
+        assert_eq!(board.groups[0].id, group);
+        assert_eq!(task_names(&board), ["A", "B", "C"]);
+    }
+
+    #[test]
+    fn subitems_wait_for_their_task() {
+        let mut board = Board::default();
+        let (task, subitem) = task_with_subitem(&mut board);
+        board.apply(BoardAction::DeleteSubitem { subitem });
+        board.apply(BoardAction::DeleteTask { task });
+        assert!(!board.can_restore(&board.trash[0]));
+        board.apply(BoardAction::Restore { id: subitem });
+        assert_eq!(board.trash.len(), 2);
+
+        board.apply(BoardAction::Restore { id: task });
+        assert!(board.can_restore(&board.trash[0]));
+        board.apply(BoardAction::Restore { id: subitem });
+        assert!(board.trash.is_empty());
+        assert_eq!(board.task(task).unwrap().subitems[0].id, subitem);
+    }
+
+    #[test]
+    fn purged_items_are_gone() {
+        let mut board = Board::default();
+        let (task, subitem) = task_with_subitem(&mut board);
+        board.apply(BoardAction::DeleteSubitem { subitem });
+        board.apply(BoardAction::DeleteTask { task });
+        board.apply(BoardAction::Purge { id: task });
+        assert_eq!(board.trash.len(), 1);
+        assert_eq!(board.trash[0].id(), subitem);
+        board.apply(BoardAction::Restore { id: task });
+        assert!(board.task(task).is_none());
+
+        board.apply(BoardAction::EmptyTrash);
+        assert!(board.trash.is_empty());
+    }
+
+    #[test]
+    fn values_must_fit_their_column() {
+        let mut board = Board::default();
+        let (task, subitem) = task_with_subitem(&mut board);
+        let task_notes = board.column_of_kind(ColumnScope::Task, CellKind::LongText);
+        let subitem_notes = board.column_of_kind(ColumnScope::Subitem, CellKind::LongText);
+        let money = CellValue::Money(Some(Money { cents: 100 }));
+        let text = CellValue::LongText("Kickoff".to_string());
+
+        let before = board.clone();
+        board.apply(BoardAction::UpdateTask {
+            task,
+            column: task_notes,
+            value: money.clone(),
+        });
+        board.apply(BoardAction::UpdateSubitem {
+            subitem,
+            column: subitem_notes,
+            value: money,
+        });
+        // The right kind, but a task column.
+        board.apply(BoardAction::UpdateSubitem {
+            subitem,
+            column: task_notes,
+            value: text.clone(),
+        });
+        assert!(board == before);
+
+        board.apply(BoardAction::UpdateTask {
+            task,
+            column: task_notes,
+            value: text.clone(),
+        });
+        assert_eq!(
+            board.task(task).unwrap().cells.get(&task_notes),
+            Some(&text)
+        );
+    }
+
+    #[test]
+    fn attached_files_keep_earlier_uploads() {
+        let mut board = Board::default();
+        let group = board.groups[0].id;
+        board.apply(BoardAction::AddTask {
+            group,
+            name: "Task".to_string(),
+        });
+        let task = board.groups[0].tasks[0].id;
+        let column = board.column_of_kind(ColumnScope::Task, CellKind::Files);
+        // Two uploads that started from the same empty cell.
+        for name in ["a.png", "b.png"] {
+            board.apply(BoardAction::AttachFiles {
+                scope: ColumnScope::Task,
+                item: task,
+                column: Some(column),
+                files: vec![file(name)],
+            });
+        }
+        assert_eq!(
+            board.task(task).unwrap().cells.get(&column),
+            Some(&CellValue::Files(vec![file("a.png"), file("b.png")]))
+        );
+    }
+
+    #[test]
+    fn attaching_without_a_column_finds_one_when_applied() {
+        let mut board = Board::default();
+        let group = board.groups[0].id;
+        board.apply(BoardAction::AddTask {
+            group,
+            name: "Task".to_string(),
+        });
+        let task = board.groups[0].tasks[0].id;
+        // The files column the upload started with goes away meanwhile.
+        let old = board.column_of_kind(ColumnScope::Task, CellKind::Files);
+        board.apply(BoardAction::RemoveColumn { column: old });
+        board.apply(BoardAction::AddColumn {
+            scope: ColumnScope::Task,
+            name: "Scans".to_string(),
+            kind: CellKind::Files,
+        });
+        let new = board.column_of_kind(ColumnScope::Task, CellKind::Files);
+
+        let attach = BoardAction::AttachFiles {
+            scope: ColumnScope::Task,
+            item: task,
+            column: Some(old),
+            files: vec![file("a.png")],
+        };
+        let before = board.clone();
+        board.apply(attach);
+        assert!(board == before);
+
+        board.apply(BoardAction::AttachFiles {
+            scope: ColumnScope::Task,
+            item: task,
+            column: None,
+            files: vec![file("b.png")],
+        });
+        assert_eq!(
+            board.task(task).unwrap().cells.get(&new),
+            Some(&CellValue::Files(vec![file("b.png")]))
+        );
+    }
+
+    #[test]
+    fn files_columns_have_no_default() {
+        let mut board = Board::default();
+        let column = board.column_of_kind(ColumnScope::Task, CellKind::Files);
+        board.apply(BoardAction::SetColumnDefault {
+            column,
+            value: CellValue::Files(vec![file("shared.png")]),
+        });
+        assert_eq!(
+            board.column_mut(column).unwrap().default,
+            CellValue::Files(Vec::new())
+        );
+    }
+
+    /// Renames the first group, one undo step per call.
+    fn rename(store: Rc<BoardStore>, name: &str) -> Rc<BoardStore> {
+        let group = store.groups[0].id;
+        store.reduce(BoardAction::RenameGroup {
+            group,
+            name: name.to_string(),
+        })
+    }
+
+    fn name(store: &BoardStore) -> &str {
+        &store.groups[0].name
+    }
+
+    #[test]
+    fn undo_and_redo_step_through_edits() {
+        let store = Rc::new(BoardStore::new(Board::default()));
+        let original = name(&store).to_string();
+        let store = rename(rename(store, "A"), "B");
+        let store = store.reduce(BoardAction::Undo);
+        assert_eq!(name(&store), "A");
+        let store = store.reduce(BoardAction::Undo);
+        assert_eq!(name(&store), original);
+        assert!(!store.can_undo());
+        let store = store.reduce(BoardAction::Redo);
+        assert_eq!(name(&store), "A");
+        let store = store.reduce(BoardAction::Redo);
+        assert_eq!(name(&store), "B");
+        assert!(!store.can_redo());
+    }
+
+    #[test]
+    fn a_new_edit_clears_redo() {
+        let store = rename(Rc::new(BoardStore::new(Board::default())), "A");
+        let store = store.reduce(BoardAction::Undo);
+        assert!(store.can_redo());
+        let store = rename(store, "C");
+        assert!(!store.can_redo());
+        let store = store.reduce(BoardAction::Redo);
+        assert_eq!(name(&store), "C");
+        // An edit that changes nothing keeps it.
+        let store = store.reduce(BoardAction::Undo);
+        let same = name(&store).to_string();
+        let store = rename(store, &same);
+        assert!(store.can_redo());
+    }
+
+    #[test]
+    fn history_is_bounded() {
+        let mut store = Rc::new(BoardStore::new(Board::default()));
+        for step in 0..=HISTORY_LIMIT {
+            store = rename(store, &step.to_string());
+        }
+        for _ in 0..HISTORY_LIMIT {
+            assert!(store.can_undo());
+            store = store.reduce(BoardAction::Undo);
+        }
+        // The oldest edit fell off the end.
+        assert!(!store.can_undo());
+        assert_eq!(name(&store), "0");
+    }
+}
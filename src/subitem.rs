@@ -1,96 +1,134 @@
-use web_sys::HtmlInputElement;
-use web_sys::KeyboardEvent;
+use crate::capture::CaptureDialog;
+use crate::cell::{CellValue, FileRef};
+use crate::column::{grid_template, ColumnScope};
+use crate::date::{due, today};
+use crate::detail::{use_open_item, ItemRef};
+use crate::editor::CellEditor;
+use crate::filter::{BoardFilter, Matches, NameInput};
+use crate::menu::{MenuItem, RowMenu};
+use crate::store::{use_board, BoardAction, Id};
 use yew::prelude::*;
 
 #[derive(Properties, PartialEq)]
 pub struct SubitemProps {
-    pub name: String,
+    pub id: Id,
     pub color: String,
-    pub on_update_name: Callback<String>,
-    pub on_update_date: Callback<String>,
-    pub on_update_area: Callback<String>,
-    pub on_update_people: Callback<String>,
-    pub on_update_notes: Callback<String>,
-    pub on_update_files: Callback<String>,
-    pub on_update_budget: Callback<String>,
+    #[prop_or_default]
+    pub filter: BoardFilter,
 }
 
 #[function_component(Subitem)]
 pub fn subitem(props: &SubitemProps) -> Html {
-    let subitem_name = use_state(|| props.name.clone());
-    let date = use_state(|| "2024-08-11".to_string());
-    let area = use_state(|| "Area 2".to_string());
-    let people = use_state(|| "Person 1".to_string());
-    let notes = use_state(|| "Sample Subitem Note".to_string());
-    let files = use_state(|| "0".to_string());
-    let budget = use_state(|| "$0".to_string());
+    let board = use_board();
+    let open_item = use_open_item();
+    let is_capturing = use_state(|| false);
 
-    {
-        let subitem_name = subitem_name.clone();
-        use_effect_with(props.name.clone(), move |name| {
-            subitem_name.set(name.clone());
-            || ()
-        });
-    }
+    let subitem_id = props.id;
+    let Some(subitem) = board.subitem(subitem_id) else {
+        return html! {};
+    };
+
+    let on_rename = {
+        let board = board.clone();
+        Callback::from(move |name: String| {
+            board.dispatch(BoardAction::RenameSubitem {
+                subitem: subitem_id,
+                name,
+            });
+        })
+    };
+
+    let on_cell_commit = |column: Id| {
+        let board = board.clone();
+        Callback::from(move |value: CellValue| {
+            board.dispatch(BoardAction::UpdateSubitem {
+                subitem: subitem_id,
+                column,
+                value,
+            });
+        })
+    };
+
+    let on_cell_attach = |column: Id| {
+        let board = board.clone();
+        Callback::from(move |files: Vec<FileRef>| {
+            board.dispatch(BoardAction::AttachFiles {
+                scope: ColumnScope::Subitem,
+                item: subitem_id,
+                column: Some(column),
+                files,
+            });
+        })
+    };
+
+    let on_open = {
+        let open_item = open_item.clone();
+        Callback::from(move |_| {
+            open_item.set(Some(ItemRef {
+                scope: ColumnScope::Subitem,
+                id: subitem_id,
+            }));
+        })
+    };
 
-    let on_input_change = |setter: UseStateHandle<String>, on_update: Callback<String>| {
-        Callback::from(move |e: KeyboardEvent| {
-            if e.key() == "Enter" {
-                if let Some(input) = e.target_dyn_into::<HtmlInputElement>() {
-                    let value = input.value();
-                    setter.set(value.clone());
-                    on_update.emit(value);
-                }
-            }
+    let on_delete = {
+        let board = board.clone();
+        Callback::from(move |_| {
+            board.dispatch(BoardAction::DeleteSubitem {
+                subitem: subitem_id,
+            });
         })
     };
 
+    let on_capture = {
+        let is_capturing = is_capturing.clone();
+        Callback::from(move |_| is_capturing.set(true))
+    };
+
+    let on_capture_close = {
+        let is_capturing = is_capturing.clone();
+        Callback::from(move |_| is_capturing.set(false))
+    };
+
     html! {
-        <li class={format!("ml-3 p-2 border rounded-lg shadow-sm my-1 border-l-8 border-{}-500", props.color)}>
-            <div class="grid grid-cols-7 gap-4">
-                <input
-                    class="text-sm border-none focus:ring-0 focus:border-blue-300 rounded-lg w-full"
-                    type="text"
-                    value={(*subitem_name).clone()}
-                    onkeydown={on_input_change(subitem_name.clone(), props.on_update_name.clone())}
-                />
-                <input
-                    class="text-sm border-none focus:ring-0 focus:border-blue-300 rounded-lg w-full"
-                    type="text"
-                    value={(*date).clone()}
-                    onkeydown={on_input_change(date.clone(), props.on_update_date.clone())}
-                />
-                <input
-                    class="text-sm border-none focus:ring-0 focus:border-blue-300 rounded-lg w-full"
-                    type="text"
-                    value={(*area).clone()}
-                    onkeydown={on_input_change(area.clone(), props.on_update_area.clone())}
-                />
-                <input
-                    class="text-sm border-none focus:ring-0 focus:border-blue-300 rounded-lg w-full"
-                    type="text"
-                    value={(*people).clone()}
-                    onkeydown={on_input_change(people.clone(), props.on_update_people.clone())}
-                />
-                <input
-                    class="text-sm border-none focus:ring-0 focus:border-blue-300 rounded-lg w-full"
-                    type="text"
-                    value={(*notes).clone()}
-                    onkeydown={on_input_change(notes.clone(), props.on_update_notes.clone())}
-                />
-                <input
-                    class="text-sm border-none focus:ring-0 focus:border-blue-300 rounded-lg w-full"
-                    type="text"
-                    value={(*files).clone()}
-                    onkeydown={on_input_change(files.clone(), props.on_update_files.clone())}
-                />
-                <input
-                    class="text-sm border-none focus:ring-0 focus:border-blue-300 rounded-lg w-full"
-                    type="text"
-                    value={(*budget).clone()}
-                    onkeydown={on_input_change(budget.clone(), props.on_update_budget.clone())}
-                />
+        <li class={format!(
+            "ml-3 p-2 border rounded-lg shadow-sm my-1 border-l-8 border-{}-500 {}",
+            props.color,
+            due(&board.subitem_columns, &subitem.cells, today()).map_or("", |due| due.row_class()),
+        )}>
+            <div class="grid gap-4" style={grid_template(&board.subitem_columns)}>
+                <div class="flex items-center">
+                    <NameInput
+                        class="text-sm w-full"
+                        name={subitem.name.clone()}
+                        query={props.filter.text.clone()}
+                        on_rename={on_rename}
+                    />
+                    <button class="px-1 text-gray-400 hover:text-blue-600" onclick={on_open.reform(|_| ())} title="Open details">{"⤢"}</button>
+                    <RowMenu items={vec![
+                        MenuItem::new("Capture image", on_capture),
+                        MenuItem::new("Delete", on_delete),
+                    ]} />
+                </div>
+                { for board.subitem_columns.iter().map(|column| html! {
+                    <CellEditor
+                        key={column.id}
+                        class="rounded-lg"
+                        value={column.cell(&subitem.cells)}
+                        on_commit={on_cell_commit(column.id)}
+                        on_attach={on_cell_attach(column.id)}
+                        options={column.options.clone()}
+                        currency={column.currency}
+                        on_open={on_open.clone()}
+                    />
+                }) }
             </div>
+            <Matches cells={props.filter.matching_cells(&board, &board.subitem_columns, &subitem.cells)} query={props.filter.text.clone()} />
+            { if *is_capturing {
+                html! { <CaptureDialog scope={ColumnScope::Subitem} item={subitem_id} on_close={on_capture_close} /> }
+            } else {
+                html! {}
+            }}
         </li>
     }
 }
@@ -0,0 +1,368 @@
+use crate::cell::{CellKind, CellValue, Currency, Money};
+use crate::column::{grid_template, ColumnDef};
+use crate::status::StatusBar;
+use crate::store::{use_board, Board, BoardAction, Cells, Id};
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use web_sys::HtmlSelectElement;
+use yew::prelude::*;
+
+/// Sums of money kept apart by currency, since amounts in different
+/// currencies can't be added. `None` once a currency's sum overflows.
+pub type Totals = BTreeMap<Currency, Option<Money>>;
+
+/// Shown in place of a sum too large to hold.
+const OVERFLOW: &str = "Overflow";
+
+/// Adds every money cell of an item to `totals`.
+fn add_money(totals: &mut Totals, columns: &[ColumnDef], cells: &Cells) {
+    for column in columns
+        .iter()
+        .filter(|column| column.kind == CellKind::Money)
+    {
+        if let CellValue::Money(Some(money)) = column.cell(cells) {
+            let total = totals
+                .entry(column.currency)
+                .or_insert(Some(Money::default()));
+            *total = total.and_then(|total| total + money);
+        }
+    }
+}
+
+/// Money across every task on the board, and their subitems if asked.
+pub fn board_totals(board: &Board, include_subitems: bool) -> Totals {
+    let mut totals = Totals::new();
+    for task in board.groups.iter().flat_map(|group| group.tasks.iter()) {
+        add_money(&mut totals, &board.columns, &task.cells);
+        if include_subitems {
+            for subitem in &task.subitems {
+                add_money(&mut totals, &board.subitem_columns, &subitem.cells);
+            }
+        }
+    }
+    totals
+}
+
+/// e.g. "$1,250.00 + €30.00", or "$0.00" when there is nothing to add.
+pub fn format_totals(totals: &Totals) -> String {
+    if totals.is_empty() {
+        return Currency::default().format(Money::default());
+    }
+    totals
+        .iter()
+        .map(|(currency, money)| match money {
+            Some(money) => currency.format(*money),
+            None => format!("{} ({})", OVERFLOW, currency.code()),
+        })
+        .collect::<Vec<_>>()
+        .join(" + ")
+}
+
+/// How a group's footer sums up one column.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum Aggregate {
+    Sum,
+    Average,
+    Min,
+    Max,
+    /// Earliest to latest date.
+    Range,
+    /// How many items have each label or option.
+    Distribution,
+    /// How many items have the cell filled in.
+    Filled,
+    Hidden,
+}
+
+impl Aggregate {
+    /// The aggregates that make sense for a kind of column, default first.
+    pub fn choices(kind: CellKind) -> &'static [Aggregate] {
+        use Aggregate::*;
+        match kind {
+            CellKind::Money => &[Sum, Average, Min, Max, Filled, Hidden],
+            CellKind::Date | CellKind::Timeline => &[Range, Filled, Hidden],
+            CellKind::Status | CellKind::Select => &[Distribution, Filled, Hidden],
+            CellKind::Person | CellKind::LongText | CellKind::Files | CellKind::Outreach => {
+                &[Filled, Hidden]
+            }
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Aggregate::Sum => "Sum",
+            Aggregate::Average => "Average",
+            Aggregate::Min => "Min",
+            Aggregate::Max => "Max",
+            Aggregate::Range => "Range",
+            Aggregate::Distribution => "Distribution",
+            Aggregate::Filled => "Filled",
+            Aggregate::Hidden => "None",
+        }
+    }
+}
+
+/// The aggregate a column's footer shows: its own choice if that still fits
+/// the column's kind, otherwise the kind's default.
+pub fn aggregate_for(column: &ColumnDef) -> Aggregate {
+    let choices = Aggregate::choices(column.kind);
+    column
+        .summary
+        .filter(|aggregate| choices.contains(aggregate))
+        .unwrap_or(choices[0])
+}
+
+/// The mean, rounded half away from zero to the nearest cent.
+fn average(amounts: &[Money]) -> Option<Money> {
+    if amounts.is_empty() {
+        return None;
+    }
+    let count = amounts.len() as i128;
+    let total: i128 = amounts.iter().map(|money| money.cents as i128).sum();
+    let rounded = (2 * total + total.signum() * count) / (2 * count);
+    Some(Money {
+        cents: rounded as i64,
+    })
+}
+
+fn date_range(dates: &[NaiveDate]) -> Option<String> {
+    let first = *dates.iter().min()?;
+    let last = *dates.iter().max()?;
+    let format = if first.year() == last.year() {
+        "%b %-d"
+    } else {
+        "%b %-d, %Y"
+    };
+    Some(if first == last {
+        first.format(format).to_string()
+    } else {
+        format!("{} – {}", first.format(format), last.format(format))
+    })
+}
+
+/// Renders one footer cell: `aggregate` applied to `values`, every cell of
+/// `column` in the group.
+fn summarize(column: &ColumnDef, values: &[CellValue], aggregate: Aggregate) -> Html {
+    let amounts: Vec<Money> = values
+        .iter()
+        .filter_map(|value| match value {
+            CellValue::Money(money) => *money,
+            _ => None,
+        })
+        .collect();
+    let money = |money: Option<Money>| {
+        money.map_or_else(|| "-".to_string(), |money| column.currency.format(money))
+    };
+    let text = match aggregate {
+        Aggregate::Hidden => return html! {},
+        Aggregate::Sum => Money::total(amounts.iter().copied()).map_or_else(
+            || OVERFLOW.to_string(),
+            |total| column.currency.format(total),
+        ),
+        Aggregate::Average => money(average(&amounts)),
+        Aggregate::Min => money(amounts.iter().min().copied()),
+        Aggregate::Max => money(amounts.iter().max().copied()),
+        Aggregate::Range => {
+            let dates: Vec<NaiveDate> = values
+                .iter()
+                .flat_map(|value| match value {
+                    CellValue::Date(date) => date.iter().copied().collect(),
+                    CellValue::Timeline(Some(timeline)) => vec![timeline.start, timeline.end],
+                    _ => Vec::new(),
+                })
+                .collect();
+            date_range(&dates).unwrap_or_else(|| "-".to_string())
+        }
+        Aggregate::Distribution if column.kind == CellKind::Status => {
+            let statuses: Vec<Option<Id>> = values
+                .iter()
+                .map(|value| match value {
+                    CellValue::Status(status) => *status,
+                    _ => None,
+                })
+                .collect();
+            return html! { <StatusBar values={statuses} /> };
+        }
+        Aggregate::Distribution => {
+            let mut counts: Vec<(String, usize)> = Vec::new();
+            for value in values {
+                if let CellValue::Select(Some(option)) = value {
+                    match counts.iter_mut().find(|(name, _)| name == option) {
+                        Some((_, count)) => *count += 1,
+                        None => counts.push((option.clone(), 1)),
+                    }
+                }
+            }
+            counts.sort_by_key(|(name, _)| {
+                column
+                    .options
+                    .iter()
+                    .position(|option| option == name)
+                    .unwrap_or(usize::MAX)
+            });
+            if counts.is_empty() {
+                "-".to_string()
+            } else {
+                counts
+                    .iter()
+                    .map(|(name, count)| format!("{} ({})", name, count))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            }
+        }
+        Aggregate::Filled => {
+            let filled = values.iter().filter(|value| !value.is_empty()).count();
+            format!("{}/{} filled", filled, values.len())
+        }
+    };
+    html! { <span>{ text }</span> }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct GroupFooterProps {
+    /// The tasks the group shows, after filtering.
+    pub tasks: Vec<Id>,
+}
+
+/// A row under a group's tasks summing up each column, in the way chosen
+/// from the small menu under it.
+#[function_component(GroupFooter)]
+pub fn group_footer(props: &GroupFooterProps) -> Html {
+    let board = use_board();
+    let tasks: Vec<_> = props
+        .tasks
+        .iter()
+        .filter_map(|id| board.task(*id))
+        .collect();
+
+    html! {
+        <div class="grid gap-0 ml-4 px-3 py-2 text-sm font-semibold text-gray-700 border-t" style={grid_template(&board.columns)}>
+            <span class="pl-6">{"Summary"}</span>
+            { for board.columns.iter().map(|column| {
+                let id = column.id;
+                let aggregate = aggregate_for(column);
+                let choices = Aggregate::choices(column.kind);
+                let values: Vec<CellValue> = tasks
+                    .iter()
+                    .map(|task| column.cell(&task.cells))
+                    .collect();
+                let on_change = {
+                    let board = board.clone();
+                    Callback::from(move |e: Event| {
+                        if let Some(select) = e.target_dyn_into::<HtmlSelectElement>() {
+                            if let Some(aggregate) = choices.get(select.selected_index() as usize) {
+                                board.dispatch(BoardAction::SetColumnSummary { column: id, aggregate: *aggregate });
+                            }
+                        }
+                    })
+                };
+                html! {
+                    <div key={id} class="flex flex-col pr-2">
+                        { summarize(column, &values, aggregate) }
+                        <select class="text-xs font-normal text-gray-400 bg-transparent border-none p-0" onchange={on_change}>
+                            { for choices.iter().map(|choice| html! {
+                                <option selected={*choice == aggregate}>{ choice.label() }</option>
+                            }) }
+                        </select>
+                    </div>
+                }
+            }) }
+        </div>
+    }
+}
+
+/// The board-wide money total shown in the toolbar.
+#[function_component(BoardTotal)]
+pub fn board_total() -> Html {
+    let board = use_board();
+    let include_subitems = use_state(|| false);
+
+    let on_toggle = {
+        let include_subitems = include_subitems.clone();
+        Callback::from(move |_| {
+            include_subitems.set(!*include_subitems);
+        })
+    };
+
+    html! {
+        <div class="mt-4 text-sm">
+            <span class="font-semibold">{"Board total: "}</span>
+            { format_totals(&board_totals(&board, *include_subitems)) }
+            <label class="ml-4 text-gray-600">
+                <input class="mr-1" type="checkbox" checked={*include_subitems} onclick={on_toggle} />
+                {"Include subitems"}
+            </label>
+        </div>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn money(cents: &[i64]) -> Vec<Money> {
+        cents.iter().map(|cents| Money { cents: *cents }).collect()
+    }
+
+    fn average_of(cents: &[i64]) -> Option<i64> {
+        average(&money(cents)).map(|money| money.cents)
+    }
+
+    #[test]
+    fn average_rounds_half_away_from_zero() {
+        assert_eq!(average_of(&[]), None);
+        assert_eq!(average_of(&[100, 200, 600]), Some(300));
+        assert_eq!(average_of(&[1, 2]), Some(2));
+        assert_eq!(average_of(&[-1, -2]), Some(-2));
+        assert_eq!(average_of(&[1, 0, 0]), Some(0));
+        assert_eq!(average_of(&[2, 0, 0]), Some(1));
+        assert_eq!(average_of(&[-2, 0, 0]), Some(-1));
+    }
+
+    #[test]
+    fn average_does_not_overflow() {
+        assert_eq!(average_of(&[i64::MAX, i64::MAX]), Some(i64::MAX));
+        assert_eq!(average_of(&[i64::MIN, i64::MIN]), Some(i64::MIN));
+        // The mean is half a cent below zero.
+        assert_eq!(average_of(&[i64::MAX, i64::MIN]), Some(-1));
+    }
+
+    #[test]
+    fn date_ranges_drop_the_year_when_shared() {
+        let day = |year, month, day| NaiveDate::from_ymd_opt(year, month, day).unwrap();
+        assert_eq!(date_range(&[]), None);
+        assert_eq!(
+            date_range(&[day(2024, 3, 9), day(2024, 1, 2)]).as_deref(),
+            Some("Jan 2 – Mar 9")
+        );
+        assert_eq!(
+            date_range(&[day(2024, 12, 30), day(2025, 1, 2)]).as_deref(),
+            Some("Dec 30, 2024 – Jan 2, 2025")
+        );
+        assert_eq!(date_range(&[day(2024, 5, 1)]).as_deref(), Some("May 1"));
+    }
+
+    #[test]
+    fn totals_keep_currencies_apart() {
+        assert_eq!(format_totals(&Totals::new()), "$0.00");
+        let mut totals = Totals::new();
+        totals.insert(Currency::Usd, Some(Money { cents: 125000 }));
+        totals.insert(Currency::Eur, Some(Money { cents: 3000 }));
+        assert_eq!(format_totals(&totals), "$1,250.00 + €30.00");
+    }
+
+    #[test]
+    fn overflowing_totals_are_marked() {
+        let budget = ColumnDef::new(1, "Budget".to_string(), CellKind::Money);
+        let columns = [budget];
+        let mut totals = Totals::new();
+        for cents in [i64::MAX, 1, -1] {
+            let cells = Cells::from([(1, CellValue::Money(Some(Money { cents })))]);
+            add_money(&mut totals, &columns, &cells);
+        }
+        assert_eq!(totals[&Currency::Usd], None);
+        totals.insert(Currency::Eur, Some(Money { cents: 3000 }));
+        assert_eq!(format_totals(&totals), "Overflow (USD) + €30.00");
+    }
+}
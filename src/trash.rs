@@ -0,0 +1,77 @@
+use crate::store::{use_board, BoardAction, TrashedItem};
+use yew::prelude::*;
+
+/// Lists everything deleted from the board, newest first, with buttons to
+/// restore or purge each entry.
+#[function_component(TrashPanel)]
+pub fn trash_panel() -> Html {
+    let board = use_board();
+
+    let on_empty = {
+        let board = board.clone();
+        Callback::from(move |_| {
+            board.dispatch(BoardAction::EmptyTrash);
+        })
+    };
+
+    html! {
+        <div class="mt-4 p-4 border rounded-lg bg-gray-50">
+            <div class="flex items-center justify-between">
+                <span class="font-semibold">{"Trash"}</span>
+                <button
+                    class="text-sm text-red-600 disabled:text-gray-400"
+                    disabled={board.trash.is_empty()}
+                    onclick={on_empty}
+                >
+                    {"Empty trash"}
+                </button>
+            </div>
+            { if board.trash.is_empty() {
+                html! { <p class="mt-2 text-sm text-gray-500">{"Nothing here."}</p> }
+            } else {
+                html! {
+                    <ul class="mt-2">
+                        { for board.trash.iter().rev().map(|item| {
+                            let id = item.id();
+                            let kind = match item {
+                                TrashedItem::Group { .. } => "Group",
+                                TrashedItem::Task { .. } => "Task",
+                                TrashedItem::Subitem { .. } => "Subitem",
+                            };
+                            let can_restore = board.can_restore(item);
+                            let on_restore = {
+                                let board = board.clone();
+                                Callback::from(move |_| board.dispatch(BoardAction::Restore { id }))
+                            };
+                            let on_purge = {
+                                let board = board.clone();
+                                Callback::from(move |_| board.dispatch(BoardAction::Purge { id }))
+                            };
+                            html! {
+                                <li key={id} class="flex items-center justify-between py-1 text-sm">
+                                    <span>
+                                        <span class="text-gray-500 mr-2">{ kind }</span>
+                                        { item.name() }
+                                    </span>
+                                    <span class="space-x-3">
+                                        <button
+                                            class="text-blue-600 disabled:text-gray-400"
+                                            disabled={!can_restore}
+                                            title={if can_restore { "" } else { "Its parent is no longer on the board" }}
+                                            onclick={on_restore}
+                                        >
+                                            {"Restore"}
+                                        </button>
+                                        <button class="text-red-600" onclick={on_purge}>
+                                            {"Delete forever"}
+                                        </button>
+                                    </span>
+                                </li>
+                            }
+                        }) }
+                    </ul>
+                }
+            }}
+        </div>
+    }
+}
@@ -0,0 +1,148 @@
+use crate::column::ColumnScope;
+use crate::markdown::Markdown;
+use crate::mentions::MentionTextarea;
+use crate::people::Avatar;
+use crate::store::{use_board, BoardAction, Id};
+use chrono::{DateTime, Local, Utc};
+use serde::{Deserialize, Serialize};
+use web_sys::{HtmlSelectElement, KeyboardEvent};
+use yew::prelude::*;
+
+/// A comment posted on a task or subitem.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct Update {
+    pub id: Id,
+    /// A [`crate::people::Person`] id, or `None` when nobody was picked.
+    pub author: Option<Id>,
+    pub body: String,
+    pub posted: DateTime<Utc>,
+}
+
+#[derive(Properties, PartialEq)]
+pub struct UpdatesFeedProps {
+    pub scope: ColumnScope,
+    pub item: Id,
+    /// The item's updates, oldest first.
+    pub updates: Vec<Update>,
+}
+
+/// An item's updates in the order they were posted, with a box to post a new
+/// one as someone from the people directory.
+#[function_component(UpdatesFeed)]
+pub fn updates_feed(props: &UpdatesFeedProps) -> Html {
+    let board = use_board();
+    let draft = use_state(String::new);
+    let author = use_state(|| board.me.or(board.people.first().map(|person| person.id)));
+
+    let on_draft = {
+        let draft = draft.clone();
+        Callback::from(move |text: String| draft.set(text))
+    };
+
+    let on_author = {
+        let board = board.clone();
+        let author = author.clone();
+        Callback::from(move |e: Event| {
+            if let Some(select) = e.target_dyn_into::<HtmlSelectElement>() {
+                let index = select.selected_index() as usize;
+                author.set(
+                    index
+                        .checked_sub(1)
+                        .and_then(|index| board.people.get(index))
+                        .map(|person| person.id),
+                );
+            }
+        })
+    };
+
+    let post = {
+        let board = board.clone();
+        let draft = draft.clone();
+        let author = author.clone();
+        let scope = props.scope;
+        let item = props.item;
+        Callback::from(move |_: ()| {
+            let body = draft.trim();
+            if body.is_empty() {
+                return;
+            }
+            board.dispatch(BoardAction::PostUpdate {
+                scope,
+                item,
+                author: *author,
+                body: body.to_string(),
+                posted: Utc::now(),
+            });
+            draft.set(String::new());
+        })
+    };
+
+    let on_post = {
+        let post = post.clone();
+        Callback::from(move |_| post.emit(()))
+    };
+
+    let on_keydown = Callback::from(move |e: KeyboardEvent| {
+        if e.key() == "Enter" && (e.ctrl_key() || e.meta_key()) {
+            e.prevent_default();
+            post.emit(());
+        }
+    });
+
+    html! {
+        <div class="flex flex-col space-y-3">
+            { if props.updates.is_empty() {
+                html! { <p class="text-sm text-gray-500">{"No updates yet."}</p> }
+            } else {
+                html! {}
+            }}
+            { for props.updates.iter().map(|update| {
+                let id = update.id;
+                let on_delete = {
+                    let board = board.clone();
+                    Callback::from(move |_| board.dispatch(BoardAction::DeleteUpdate { update: id }))
+                };
+                let name = update
+                    .author
+                    .and_then(|author| board.person(author))
+                    .map_or("Someone".to_string(), |person| person.name.clone());
+                html! {
+                    <div key={id} class="p-3 border rounded-lg bg-white">
+                        <div class="flex items-center mb-1 space-x-2 text-sm">
+                            { if let Some(author) = update.author {
+                                html! { <Avatar person={author} /> }
+                            } else {
+                                html! {}
+                            }}
+                            <span class="font-semibold">{ name }</span>
+                            <span class="flex-1 text-xs text-gray-500">
+                                { update.posted.with_timezone(&Local).format("%b %-d, %Y %H:%M").to_string() }
+                            </span>
+                            <button class="text-xs text-red-600" onclick={on_delete}>{"Delete"}</button>
+                        </div>
+                        <Markdown text={update.body.clone()} />
+                    </div>
+                }
+            }) }
+            <div class="flex flex-col p-3 space-y-2 border rounded-lg bg-gray-50">
+                <MentionTextarea
+                    value={(*draft).clone()}
+                    on_input={on_draft}
+                    onkeydown={on_keydown}
+                    placeholder="Write an update... @ mentions people."
+                />
+                <div class="flex items-center space-x-2 text-sm">
+                    <span class="text-gray-500">{"Posting as"}</span>
+                    <select class="border rounded p-1" onchange={on_author}>
+                        <option selected={author.is_none()}>{"Someone"}</option>
+                        { for board.people.iter().map(|person| html! {
+                            <option selected={*author == Some(person.id)}>{ &person.name }</option>
+                        }) }
+                    </select>
+                    <span class="flex-1" />
+                    <button class="p-1 px-2 bg-blue-500 text-white rounded" onclick={on_post} title="Post (Ctrl+Enter)">{"Post"}</button>
+                </div>
+            </div>
+        </div>
+    }
+}